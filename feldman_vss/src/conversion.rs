@@ -0,0 +1,127 @@
+//! Shamir/additive share conversion for the main secret, with matching commitments.
+//!
+//! Shamir to additive: within a quorum `ids`, member `i` holds `w_i = \lambda_i * x_i`
+//! with public counterpart `G * w_i = X_i * \lambda_i`, and `\sum_i w_i = x`.
+//!
+//! Additive to Shamir: each holder of `w_i` deals it with `VssLocalScheme::new_from_secret`.
+//! Everyone checks that the constant term of its commitment is the known `G * w_i`,
+//! and the commitments of all dealers sum to a commitment of the new sharing.
+
+impl KeyStore {
+    /// Additive share of the main secret within the quorum `ids`.
+    pub fn additive_share(&self, ids: &[usize]) -> Scalar {
+        lagrange_coef(self.id, ids) * self.vss_secret
+    }
+
+    /// Public counterpart of member `id`'s additive share within the quorum `ids`.
+    pub fn additive_pk_share(&self, id: usize, ids: &[usize]) -> ProjectivePoint {
+        self.pk_share(id) * lagrange_coef(id, ids)
+    }
+
+    /// `additive_pk_share` of every member of `ids`, in the same order.
+    pub fn additive_pk_shares(&self, ids: &[usize]) -> Vec<ProjectivePoint> {
+        ids.iter()
+            .zip(lagrange_coefs(ids))
+            .map(|(id, lambda)| self.pk_share(*id) * lambda)
+            .collect()
+    }
+}
+
+impl VssLocalScheme {
+    /// Random polynomial of length `t` with the given constant term, e.g. an additive share.
+    pub fn new_from_secret(secret: &Scalar, t: usize) -> Self {
+        Self::new_from_secret_with_rng(secret, t, &mut rand::thread_rng())
+    }
+
+    /// Same as `new_from_secret`, drawing the other coefficients from `rng`.
+    pub fn new_from_secret_with_rng<R: CryptoRng + RngCore>(
+        secret: &Scalar,
+        t: usize,
+        rng: &mut R,
+    ) -> Self {
+        assert!(t >= 1);
+        let mut scheme = Self::new_with_rng(t, rng);
+        scheme.poly[0] = *secret;
+        scheme
+    }
+}
+
+impl VssCommitment {
+    /// Whether the committed secret is the discrete log of `point`.
+    pub fn commits_to(&self, point: &ProjectivePoint) -> bool {
+        self.first()
+            .is_some_and(|coef_com| ProjectivePoint::from(*coef_com) == *point)
+    }
+
+    /// Commitment to the sum of the committed polynomials. All must have the same length.
+    pub fn sum(coms: &[&VssCommitment]) -> VssCommitment {
+        assert!(!coms.is_empty());
+        let t = coms[0].len();
+        assert!(
+            coms.iter().all(|com| com.len() == t),
+            "commitments of different lengths"
+        );
+        let points: Vec<AffinePoint> = (0..t)
+            .map(|m| {
+                let point: ProjectivePoint =
+                    coms.iter().map(|com| ProjectivePoint::from(com[m])).sum();
+                point.to_affine()
+            })
+            .collect();
+        VssCommitment::new_from_vec(points)
+    }
+}
+
+use crate::{lagrange_coef, lagrange_coefs, KeyStore, VssCommitment, VssLocalScheme};
+use k256::{AffinePoint, ProjectivePoint, Scalar};
+use rand::{CryptoRng, RngCore};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keystore::mock_dkg;
+
+    #[test]
+    fn test_conversion() {
+        let keystores = mock_dkg(3, &[1, 2, 3, 4, 5]);
+        let pk = keystores[0].pk();
+
+        // Shamir to additive.
+        let ids = [2, 3, 5];
+        let quorum: Vec<&KeyStore> = keystores.iter().filter(|ks| ids.contains(&ks.id)).collect();
+        let additive: Vec<Scalar> = quorum.iter().map(|ks| ks.additive_share(&ids)).collect();
+        for (ks, w) in quorum.iter().zip(additive.iter()) {
+            assert_eq!(
+                ProjectivePoint::GENERATOR * w,
+                keystores[0].additive_pk_share(ks.id, &ids)
+            );
+        }
+        assert_eq!(
+            ProjectivePoint::GENERATOR * additive.iter().sum::<Scalar>(),
+            pk
+        );
+        let pk_shares = keystores[0].additive_pk_shares(&ids);
+        for (w, pk_share) in additive.iter().zip(pk_shares.iter()) {
+            assert_eq!(ProjectivePoint::GENERATOR * w, *pk_share);
+        }
+
+        // Additive back to Shamir, with threshold 2 among members 1..=4.
+        let schemes: Vec<VssLocalScheme> = additive
+            .iter()
+            .map(|w| VssLocalScheme::new_from_secret(w, 2))
+            .collect();
+        let coms: Vec<VssCommitment> = schemes.iter().map(|s| s.commit()).collect();
+        for (ks, com) in quorum.iter().zip(coms.iter()) {
+            assert!(com.commits_to(&keystores[0].additive_pk_share(ks.id, &ids)));
+        }
+        let com_refs: Vec<&VssCommitment> = coms.iter().collect();
+        let new_com = VssCommitment::sum(&com_refs);
+        assert!(new_com.commits_to(&pk));
+
+        for id in 1..=4 {
+            let new_share: Scalar = schemes.iter().map(|s| s.share_to(id)).sum();
+            let (poly_com, polyval_com) = new_com.prepare_to_check_vss_com(id, &new_share);
+            assert_eq!(poly_com, polyval_com);
+        }
+    }
+}
@@ -0,0 +1,111 @@
+/// Chaum-Pedersen proof of discrete log equality.
+///
+/// Proves knowledge of `x` such that `X == G * x` and `Y == H * x`,
+/// without revealing `x`. Made non-interactive by Fiat-Shamir.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DleqProof {
+    pub c: Scalar, // challenge
+    pub z: Scalar, // response, z == k + c * x
+}
+
+impl DleqProof {
+    /// Prove that `G * x` and `H * x` share the same discrete log `x`.
+    #[allow(non_snake_case)]
+    pub fn prove(x: &Scalar, H: &ProjectivePoint) -> Self {
+        Self::prove_with_rng(x, H, &mut rand::thread_rng())
+    }
+
+    /// Same as `prove`, drawing the nonce from `rng`.
+    #[allow(non_snake_case)]
+    pub fn prove_with_rng<R: CryptoRng + RngCore>(
+        x: &Scalar,
+        H: &ProjectivePoint,
+        rng: &mut R,
+    ) -> Self {
+        let G = ProjectivePoint::GENERATOR;
+        let X = G * x;
+        let Y = H * x;
+
+        let k = Scalar::random(rng);
+        let A = G * k;
+        let B = H * &k;
+        let c = challenge(&X, H, &Y, &A, &B);
+        let z = k + c * x;
+        Self { c, z }
+    }
+
+    /// Verify that `X == G * x` and `Y == H * x` for some `x`.
+    #[allow(non_snake_case)]
+    pub fn verify(&self, X: &ProjectivePoint, H: &ProjectivePoint, Y: &ProjectivePoint) -> bool {
+        let G = ProjectivePoint::GENERATOR;
+        // A == G * k == G * z - X * c
+        // B == H * k == H * z - Y * c
+        let A = G * self.z - X * &self.c;
+        let B = H * &self.z - Y * &self.c;
+        self.c == challenge(X, H, Y, &A, &B)
+    }
+}
+
+/// Fiat-Shamir challenge. Binds the statement `(G, X, H, Y)` and the commitments `(A, B)`.
+#[allow(non_snake_case)]
+fn challenge(
+    X: &ProjectivePoint,
+    H: &ProjectivePoint,
+    Y: &ProjectivePoint,
+    A: &ProjectivePoint,
+    B: &ProjectivePoint,
+) -> Scalar {
+    let mut transcript = Transcript::new(b"feldman_vss/dleq");
+    transcript.append_point(b"G", &ProjectivePoint::GENERATOR);
+    transcript.append_point(b"X", X);
+    transcript.append_point(b"H", H);
+    transcript.append_point(b"Y", Y);
+    transcript.append_point(b"A", A);
+    transcript.append_point(b"B", B);
+    transcript.challenge_scalar(b"c")
+}
+
+use crate::Transcript;
+use k256::{elliptic_curve::Field, ProjectivePoint, Scalar};
+use rand::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[allow(non_snake_case)]
+    fn test_dleq() {
+        let mut rng = rand::thread_rng();
+        let x = Scalar::random(&mut rng);
+        let H = ProjectivePoint::GENERATOR * Scalar::random(&mut rng);
+        let X = ProjectivePoint::GENERATOR * x;
+        let Y = H * x;
+
+        let proof = DleqProof::prove(&x, &H);
+        assert!(proof.verify(&X, &H, &Y));
+
+        // Wrong `Y`, e.g. a different discrete log.
+        let Y_bad = H * (x + Scalar::ONE);
+        assert!(!proof.verify(&X, &H, &Y_bad));
+
+        // Wrong base.
+        let H_bad = H + ProjectivePoint::GENERATOR;
+        assert!(!proof.verify(&X, &H_bad, &Y));
+    }
+
+    #[test]
+    #[allow(non_snake_case)]
+    fn test_dleq_seeded() {
+        use rand::{rngs::StdRng, SeedableRng};
+        let x = Scalar::from(1145141919810893u64);
+        let H = ProjectivePoint::GENERATOR * Scalar::from(7u64);
+        let proof1 = DleqProof::prove_with_rng(&x, &H, &mut StdRng::seed_from_u64(42));
+        let proof2 = DleqProof::prove_with_rng(&x, &H, &mut StdRng::seed_from_u64(42));
+        assert_eq!(proof1, proof2);
+        assert!(proof1.verify(&(ProjectivePoint::GENERATOR * x), &H, &(H * x)));
+        let proof3 = DleqProof::prove_with_rng(&x, &H, &mut StdRng::seed_from_u64(43));
+        assert_ne!(proof1, proof3);
+    }
+}
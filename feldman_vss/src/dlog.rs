@@ -0,0 +1,82 @@
+//! Discrete logarithms over secp256k1 points, with the solvers of `modulo_arithmetic`.
+//!
+//! The group order is a 256-bit prime, so only exponents known to be small can be found,
+//! e.g. plaintexts of exponential ElGamal.
+
+/// The secp256k1 group. k256 writes it additively, so `op` is point addition
+/// and `pow(P, k)` is `P * k`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Secp256k1Group;
+
+impl CyclicGroup for Secp256k1Group {
+    type Elem = ProjectivePoint;
+
+    fn identity(&self) -> ProjectivePoint {
+        ProjectivePoint::IDENTITY
+    }
+
+    fn op(&self, a: &ProjectivePoint, b: &ProjectivePoint) -> ProjectivePoint {
+        a + b
+    }
+
+    fn inv(&self, a: &ProjectivePoint) -> ProjectivePoint {
+        -a
+    }
+
+    fn to_bytes(&self, a: &ProjectivePoint) -> Vec<u8> {
+        a.to_encoded_point(true).as_bytes().to_vec()
+    }
+
+    fn pow(&self, a: &ProjectivePoint, k: &BigInt) -> ProjectivePoint {
+        a * &k.to_scalar()
+    }
+}
+
+/// `m` in `[0, bound]` with `G * m == point`, by baby-step giant-step,
+/// i.e. `O(sqrt(bound))` point additions instead of `O(bound)`.
+pub fn dlog_bounded(point: &ProjectivePoint, bound: u64) -> Option<u64> {
+    let m = bsgs(
+        &Secp256k1Group,
+        &ProjectivePoint::GENERATOR,
+        point,
+        &(BigInt::from(bound) + 1u8),
+    )?;
+    m.to_u64()
+}
+
+use crate::interop::BigIntToScalar;
+use k256::{elliptic_curve::sec1::ToEncodedPoint, ProjectivePoint};
+use modulo_arithmetic::{bsgs, CyclicGroup};
+use num_bigint::BigInt;
+use num_traits::ToPrimitive;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k256::Scalar;
+    use modulo_arithmetic::prelude::const_secp256k1_order;
+
+    #[test]
+    fn test_dlog_bounded() {
+        for m in [0u64, 1, 2, 1000, 114514, 1 << 20] {
+            let point = ProjectivePoint::GENERATOR * Scalar::from(m);
+            assert_eq!(dlog_bounded(&point, 1 << 20), Some(m));
+        }
+        let point = ProjectivePoint::GENERATOR * Scalar::from((1u64 << 20) + 1);
+        assert_eq!(dlog_bounded(&point, 1 << 20), None);
+        assert_eq!(dlog_bounded(&-ProjectivePoint::GENERATOR, 1 << 20), None);
+    }
+
+    #[test]
+    fn test_secp256k1_group() {
+        let group = Secp256k1Group;
+        let g = ProjectivePoint::GENERATOR;
+        let n = const_secp256k1_order();
+        assert_eq!(group.pow(&g, &n), ProjectivePoint::IDENTITY);
+        assert_eq!(group.pow(&g, &BigInt::from(-1)), -g);
+        assert_eq!(
+            group.op(&group.pow(&g, &BigInt::from(114)), &g),
+            g * Scalar::from(115u64)
+        );
+    }
+}
@@ -0,0 +1,78 @@
+//! Threshold ECDH.
+//!
+//! Derive the shared secret `P * x` between the main secret key `x`
+//! and an outside public key `P`, without reconstructing `x`.
+//!
+//! Each attendant `i` publishes `P * x_i` together with a DLEQ proof,
+//! which ties it to the verification share `G * x_i`.
+//! Any `t` such contributions are interpolated in the exponent:
+//! $$
+//! P * x = \sum_{i} (P * x_i) * \lambda_i
+//! $$
+
+/// A member's contribution to threshold ECDH.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EcdhShare {
+    pub id: usize,
+    pub point: AffinePoint, // P * x_i
+    pub proof: DleqProof,   // log_G(G * x_i) == log_P(P * x_i)
+}
+
+impl EcdhShare {
+    pub fn new(keystore: &KeyStore, peer_pk: &ProjectivePoint) -> Self {
+        Self::new_with_rng(keystore, peer_pk, &mut rand::thread_rng())
+    }
+
+    /// Same as `new`, drawing the proof nonce from `rng`.
+    pub fn new_with_rng<R: CryptoRng + RngCore>(
+        keystore: &KeyStore,
+        peer_pk: &ProjectivePoint,
+        rng: &mut R,
+    ) -> Self {
+        let x_i = keystore.vss_secret;
+        let point = (peer_pk * &x_i).to_affine();
+        let proof = DleqProof::prove_with_rng(&x_i, peer_pk, rng);
+        Self {
+            id: keystore.id,
+            point,
+            proof,
+        }
+    }
+
+    /// Check the contribution against the verification share of its sender.
+    pub fn verify(&self, keystore: &KeyStore, peer_pk: &ProjectivePoint) -> bool {
+        let pk_share = keystore.pk_share(self.id);
+        let point = ProjectivePoint::from(self.point);
+        self.proof.verify(&pk_share, peer_pk, &point)
+    }
+}
+
+/// Combine `>= t` verified contributions into the shared secret `P * x`.
+pub fn combine_ecdh_shares(
+    keystore: &KeyStore,
+    peer_pk: &ProjectivePoint,
+    shares: &[EcdhShare],
+) -> ProjectivePoint {
+    assert!(
+        shares.len() >= keystore.t(),
+        "Need at least {} ECDH shares, got {}",
+        keystore.t(),
+        shares.len()
+    );
+    let ids: Vec<usize> = shares.iter().map(|s| s.id).collect();
+    let mut res = ProjectivePoint::IDENTITY;
+    for (share, lambda) in shares.iter().zip(lagrange_coefs(&ids)) {
+        assert!(
+            share.verify(keystore, peer_pk),
+            "ECDH share verification failed for {}",
+            share.id
+        );
+        res += ProjectivePoint::from(share.point) * lambda;
+    }
+    res
+}
+
+use crate::{lagrange_coefs, DleqProof, KeyStore};
+use k256::{AffinePoint, ProjectivePoint};
+use rand::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
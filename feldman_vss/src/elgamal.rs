@@ -0,0 +1,161 @@
+//! Threshold ElGamal over secp256k1.
+//!
+//! Anyone encrypts to the main public key `pk == G * x`:
+//! $$
+//! (C_1, C_2) = (G * r, M + pk * r)
+//! $$
+//! Decryption needs `C_1 * x`, which is exactly a threshold ECDH with `C_1`,
+//! so any `t` members give partial decryptions `C_1 * x_i` with DLEQ proofs
+//! and the combiner recovers `M = C_2 - C_1 * x`.
+//!
+//! Small integers `m` are encoded as `M = G * m` ("exponential ElGamal"),
+//! which makes ciphertexts additively homomorphic,
+//! at the price of solving a small discrete log on decryption.
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ElGamalCiphertext {
+    pub c1: AffinePoint, // G * r
+    pub c2: AffinePoint, // M + pk * r
+}
+
+/// A member's partial decryption `C_1 * x_i`, with a proof of correctness.
+pub type PartialDecryption = EcdhShare;
+
+impl ElGamalCiphertext {
+    /// Encrypt a curve point to the main public key.
+    #[allow(non_snake_case)]
+    pub fn encrypt_point(pk: &ProjectivePoint, M: &ProjectivePoint) -> Self {
+        Self::encrypt_point_with_rng(pk, M, &mut rand::thread_rng())
+    }
+
+    /// Same as `encrypt_point`, drawing the ephemeral key from `rng`.
+    #[allow(non_snake_case)]
+    pub fn encrypt_point_with_rng<R: CryptoRng + RngCore>(
+        pk: &ProjectivePoint,
+        M: &ProjectivePoint,
+        rng: &mut R,
+    ) -> Self {
+        let r = Scalar::random(rng);
+        let c1 = ProjectivePoint::GENERATOR * r;
+        let c2 = pk * &r + M;
+        Self {
+            c1: c1.to_affine(),
+            c2: c2.to_affine(),
+        }
+    }
+
+    /// Encrypt a small integer as `G * m`.
+    pub fn encrypt_u64(pk: &ProjectivePoint, m: u64) -> Self {
+        Self::encrypt_u64_with_rng(pk, m, &mut rand::thread_rng())
+    }
+
+    /// Same as `encrypt_u64`, drawing the ephemeral key from `rng`.
+    pub fn encrypt_u64_with_rng<R: CryptoRng + RngCore>(
+        pk: &ProjectivePoint,
+        m: u64,
+        rng: &mut R,
+    ) -> Self {
+        let point = ProjectivePoint::GENERATOR * Scalar::from(m);
+        Self::encrypt_point_with_rng(pk, &point, rng)
+    }
+
+    /// Partial decryption by the member owning `keystore`.
+    pub fn partial_decrypt(&self, keystore: &KeyStore) -> PartialDecryption {
+        self.partial_decrypt_with_rng(keystore, &mut rand::thread_rng())
+    }
+
+    /// Same as `partial_decrypt`, drawing the proof nonce from `rng`.
+    pub fn partial_decrypt_with_rng<R: CryptoRng + RngCore>(
+        &self,
+        keystore: &KeyStore,
+        rng: &mut R,
+    ) -> PartialDecryption {
+        EcdhShare::new_with_rng(keystore, &ProjectivePoint::from(self.c1), rng)
+    }
+
+    /// Combine `>= t` verified partial decryptions into the plaintext point `M`.
+    pub fn decrypt_point(
+        &self,
+        keystore: &KeyStore,
+        shares: &[PartialDecryption],
+    ) -> ProjectivePoint {
+        let c1 = ProjectivePoint::from(self.c1);
+        let c2 = ProjectivePoint::from(self.c2);
+        let c1_x = combine_ecdh_shares(keystore, &c1, shares);
+        c2 - c1_x
+    }
+
+    /// Combine `>= t` verified partial decryptions into the plaintext `m`,
+    /// searching `0..=bound` by `dlog_bounded`. Returns `None` if `m` is out of range.
+    pub fn decrypt_u64(
+        &self,
+        keystore: &KeyStore,
+        shares: &[PartialDecryption],
+        bound: u64,
+    ) -> Option<u64> {
+        dlog_bounded(&self.decrypt_point(keystore, shares), bound)
+    }
+}
+
+/// Homomorphic addition. Decrypts to `M_1 + M_2`, or `G * (m_1 + m_2)`.
+impl Add for &ElGamalCiphertext {
+    type Output = ElGamalCiphertext;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let c1 = ProjectivePoint::from(self.c1) + rhs.c1;
+        let c2 = ProjectivePoint::from(self.c2) + rhs.c2;
+        ElGamalCiphertext {
+            c1: c1.to_affine(),
+            c2: c2.to_affine(),
+        }
+    }
+}
+
+use std::ops::Add;
+
+use crate::{combine_ecdh_shares, dlog_bounded, EcdhShare, KeyStore};
+use k256::{elliptic_curve::Field, AffinePoint, ProjectivePoint, Scalar};
+use rand::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_elgamal() {
+        let keystores = crate::keystore::mock_dkg(2, &[1, 2, 3]);
+        let keystore = &keystores[0];
+        let pk = keystore.pk();
+
+        let ct_a = ElGamalCiphertext::encrypt_u64(&pk, 114);
+        let ct_b = ElGamalCiphertext::encrypt_u64(&pk, 514);
+        let ct_sum = &ct_a + &ct_b;
+        let parts = [
+            ct_sum.partial_decrypt(&keystores[0]),
+            ct_sum.partial_decrypt(&keystores[2]),
+        ];
+        assert_eq!(ct_sum.decrypt_u64(keystore, &parts, 1000), Some(628));
+        assert_eq!(ct_sum.decrypt_u64(keystore, &parts, 100), None);
+
+        let point = ProjectivePoint::GENERATOR * Scalar::random(&mut rand::thread_rng());
+        let ct = ElGamalCiphertext::encrypt_point(&pk, &point);
+        let parts = [
+            ct.partial_decrypt(&keystores[1]),
+            ct.partial_decrypt(&keystores[2]),
+        ];
+        assert_eq!(ct.decrypt_point(keystore, &parts), point);
+    }
+
+    #[test]
+    fn test_elgamal_seeded() {
+        use rand::{rngs::StdRng, SeedableRng};
+        let keystores = crate::keystore::mock_dkg(2, &[1, 2, 3]);
+        let pk = keystores[0].pk();
+        let encrypt = |seed: u64| {
+            ElGamalCiphertext::encrypt_u64_with_rng(&pk, 114, &mut StdRng::seed_from_u64(seed))
+        };
+        assert_eq!(encrypt(42), encrypt(42));
+        assert_ne!(encrypt(42), encrypt(43));
+    }
+}
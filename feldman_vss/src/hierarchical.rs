@@ -0,0 +1,248 @@
+//! DKG options for weighted and hierarchical access structures.
+//!
+//! Weighted: a party of weight `w` takes part in the ordinary DKG under `w` member ids,
+//! given by `WeightedKeyStore::member_ids`, and groups the resulting keystores into a
+//! `WeightedKeyStore`. Any set of parties holding `>= t` keystores can then sign, decrypt
+//! or recover as usual.
+//!
+//! Hierarchical: each dealer sends member `id` at level `i` the derivative
+//! `f^{(k_{i-1})}(id)` of its polynomial instead of `f(id)`.
+//! The share is checked against the dealer's commitment by differentiating in the exponent,
+//! and the main secret is recovered by Birkhoff interpolation.
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct HierarchicalKeyStore {
+    pub id: usize,
+    pub level: usize,
+    /// Cumulative threshold of each level, strictly increasing.
+    pub thresholds: Vec<usize>,
+    pub vss_scheme: VssLocalScheme,
+    pub vss_coms: HashMap<usize, VssCommitment>,
+    /// `F^{(k_{level-1})}(id)`, where `F` is the sum of all dealers' polynomials.
+    pub vss_secret: Scalar,
+}
+
+/// The keystores of one party in a weighted DKG, one per member id.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct WeightedKeyStore {
+    /// 1-based index of the party in `weights`.
+    pub party: usize,
+    pub keystores: Vec<KeyStore>,
+}
+
+impl VssLocalScheme {
+    /// Share the `order`-th derivative of the polynomial to a participant.
+    pub fn share_to_derivative(&self, id: usize, order: usize) -> Scalar {
+        let id = Scalar::from(id as u64);
+        eval_derivative(&self.poly, order, &id, &ScalarField)
+    }
+}
+
+impl VssCommitment {
+    /// `G * f^{(order)}(id)`, i.e. the `order`-th derivative of the committed polynomial in the exponent.
+    pub fn eval_derivative_at(&self, id: usize, order: usize) -> ProjectivePoint {
+        let x = Scalar::from(id as u64);
+
+        let mut poly_com = ProjectivePoint::IDENTITY;
+        for (m, coef_com) in self.iter().enumerate().skip(order).rev() {
+            let factor = falling_factorial(m, order, &ScalarField);
+            poly_com = (poly_com * x) + ProjectivePoint::from(*coef_com) * factor;
+        }
+        poly_com
+    }
+
+    #[allow(non_snake_case)]
+    pub fn prepare_to_check_vss_derivative(
+        &self,            // vss commitment previously received from `id`
+        id: usize,        // participant id
+        order: usize,     // order of the derivative, see `derivative_order`
+        polyval: &Scalar, // value of the derivative computed at and received from `id`
+    ) -> (String, String) {
+        let poly_com = self.eval_derivative_at(id, order);
+        let polyval_com = ProjectivePoint::GENERATOR * polyval;
+
+        let left = poly_com.diy_hash();
+        let right = polyval_com.diy_hash();
+        (left, right)
+    }
+}
+
+impl HierarchicalKeyStore {
+    /// Minimum count of shares to recover the secret, given the levels allow it.
+    pub fn t(&self) -> usize {
+        *self.thresholds.last().unwrap()
+    }
+
+    /// Order of the derivative held by this member.
+    pub fn order(&self) -> usize {
+        derivative_order(self.level, &self.thresholds)
+    }
+
+    /// Main public key
+    pub fn pk(&self) -> ProjectivePoint {
+        let mut res = ProjectivePoint::IDENTITY;
+        for com in self.vss_coms.values() {
+            res += &com[0];
+        }
+        res
+    }
+
+    /// Public verification share of member `id` at `level`.
+    pub fn pk_share(&self, id: usize, level: usize) -> ProjectivePoint {
+        let order = derivative_order(level, &self.thresholds);
+        let mut res = ProjectivePoint::IDENTITY;
+        for com in self.vss_coms.values() {
+            res += com.eval_derivative_at(id, order);
+        }
+        res
+    }
+
+    pub fn share(&self) -> HierarchicalShare<Scalar> {
+        HierarchicalShare {
+            id: self.id,
+            level: self.level,
+            val: self.vss_secret,
+        }
+    }
+}
+
+impl WeightedKeyStore {
+    /// Member ids under which `party` (1-based) takes part in the DKG.
+    pub fn member_ids(party: usize, weights: &[usize]) -> Vec<usize> {
+        assert!(
+            party >= 1 && party <= weights.len(),
+            "party {} is out of range",
+            party
+        );
+        weighted_ids(weights).swap_remove(party - 1)
+    }
+
+    /// Group the keystores `party` obtained from the DKG.
+    /// Panics unless they cover exactly its member ids.
+    pub fn new(party: usize, weights: &[usize], mut keystores: Vec<KeyStore>) -> Self {
+        keystores.sort_by_key(|ks| ks.id);
+        let ids: Vec<usize> = keystores.iter().map(|ks| ks.id).collect();
+        assert_eq!(
+            ids,
+            Self::member_ids(party, weights),
+            "keystores do not match the member ids of party {}",
+            party
+        );
+        Self { party, keystores }
+    }
+
+    /// Count of keystores held, i.e. the weight of the party.
+    pub fn weight(&self) -> usize {
+        self.keystores.len()
+    }
+
+    /// Minimum total weight to recover the secret.
+    pub fn t(&self) -> usize {
+        self.keystores[0].t()
+    }
+
+    /// Main public key
+    pub fn pk(&self) -> ProjectivePoint {
+        self.keystores[0].pk()
+    }
+
+    pub fn share(&self) -> WeightedShare<Scalar> {
+        WeightedShare {
+            party: self.party,
+            shares: self
+                .keystores
+                .iter()
+                .map(|ks| ShamirShare {
+                    id: ks.id,
+                    val: ks.vss_secret,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Recover the main secret from the shares of parties with total weight `>= t`.
+/// `None` if their weight is too low.
+pub fn recover_weighted(shares: &[&WeightedShare<Scalar>], t: usize) -> Option<Scalar> {
+    if !weighted_can_reconstruct(shares, t) {
+        return None;
+    }
+    Some(reconstruct_weighted(shares, &ScalarField))
+}
+
+/// Recover the main secret from the shares of an authorized set.
+/// `None` if the set cannot reconstruct.
+pub fn recover_hierarchical(
+    shares: &[&HierarchicalShare<Scalar>],
+    thresholds: &[usize],
+) -> Option<Scalar> {
+    reconstruct_hierarchical(shares, thresholds, &ScalarField)
+}
+
+use std::collections::HashMap;
+
+use crate::{util::hash::DiyHash, KeyStore, VssCommitment, VssLocalScheme};
+use k256::{ProjectivePoint, Scalar};
+use serde::{Deserialize, Serialize};
+use shamir_secret_sharing::{
+    derivative_order, eval_derivative, falling_factorial, reconstruct_hierarchical,
+    reconstruct_weighted, weighted_can_reconstruct, weighted_ids, HierarchicalShare, ScalarField,
+    ShamirShare, WeightedShare,
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keystore::mock_dkg;
+
+    #[test]
+    fn test_vss_derivative() {
+        let vss = VssLocalScheme::new(5);
+        let com = vss.commit();
+        for order in 0..=5 {
+            for id in 1..=4 {
+                let share = vss.share_to_derivative(id, order);
+                let (poly_com, polyval_com) =
+                    com.prepare_to_check_vss_derivative(id, order, &share);
+                assert_eq!(poly_com, polyval_com, "failed at id={} order={}", id, order);
+            }
+        }
+        assert_eq!(vss.share_to_derivative(3, 0), vss.share_to(3));
+    }
+
+    #[test]
+    fn test_weighted_dkg() {
+        // Party 1 has weight 2, others weight 1.
+        let weights = [2, 1, 1, 1];
+        let ids: Vec<usize> = (1..=weights.len())
+            .flat_map(|party| WeightedKeyStore::member_ids(party, &weights))
+            .collect();
+        assert_eq!(ids, vec![1, 2, 3, 4, 5]);
+        let mut keystores = mock_dkg(3, &ids).into_iter();
+        let parties: Vec<WeightedKeyStore> = weights
+            .iter()
+            .enumerate()
+            .map(|(i, w)| {
+                WeightedKeyStore::new(i + 1, &weights, keystores.by_ref().take(*w).collect())
+            })
+            .collect();
+        assert_eq!(parties[0].weight(), 2);
+        assert_eq!(parties[0].t(), 3);
+
+        // Party 1 and party 3 hold 3 keystores.
+        let shares = [parties[0].share(), parties[2].share()];
+        let sk = recover_weighted(&[&shares[0], &shares[1]], 3).unwrap();
+        assert_eq!(ProjectivePoint::GENERATOR * sk, parties[0].pk());
+
+        // Party 2 and party 3 only hold 2.
+        let shares = [parties[1].share(), parties[2].share()];
+        assert_eq!(recover_weighted(&[&shares[0], &shares[1]], 3), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "keystores do not match the member ids of party 2")]
+    fn test_weighted_keystore_mismatch() {
+        let keystores = mock_dkg(2, &[1, 2, 3]);
+        WeightedKeyStore::new(2, &[2, 1], keystores[..1].to_vec());
+    }
+}
@@ -3,7 +3,7 @@ pub struct KeyStore {
     pub id: usize,
     pub vss_scheme: VssLocalScheme,
     pub vss_coms: HashMap<usize, VssCommitment>,
-    pub vss_secret: BigInt,
+    pub vss_secret: Scalar,
 }
 
 #[allow(dead_code)]
@@ -15,7 +15,9 @@ impl KeyStore {
 
     /// Minimum count of shares to recover the secret
     pub fn t(&self) -> usize {
-        self.vss_scheme.t()
+        // Every commitment has length t, as checked during DKG.
+        // Unlike `vss_scheme`, the commitments survive share repair.
+        self.vss_coms.values().next().map_or(0, |com| com.len())
     }
 
     /// Main public key
@@ -27,11 +29,40 @@ impl KeyStore {
         }
         res
     }
+
+    /// Public verification share of member `id`, i.e. `G * x_id`.
+    /// Computed from the VSS commitments, so anyone holding a keystore can check it.
+    pub fn pk_share(&self, id: usize) -> ProjectivePoint {
+        let mut res = ProjectivePoint::IDENTITY;
+        for com in self.vss_coms.values() {
+            res += com.eval_at(id);
+        }
+        res
+    }
 }
 
 use std::collections::HashMap;
 
 use crate::{VssCommitment, VssLocalScheme};
-use k256::ProjectivePoint;
-use num_bigint::BigInt;
+use k256::{ProjectivePoint, Scalar};
 use serde::{Deserialize, Serialize};
+
+/// Run DKG among `ids` in-process, for unit tests.
+#[cfg(test)]
+pub(crate) fn mock_dkg(t: usize, ids: &[usize]) -> Vec<KeyStore> {
+    let schemes: Vec<VssLocalScheme> = ids.iter().map(|_| VssLocalScheme::new(t)).collect();
+    let vss_coms: HashMap<usize, VssCommitment> = ids
+        .iter()
+        .zip(schemes.iter())
+        .map(|(id, scheme)| (*id, scheme.commit()))
+        .collect();
+    ids.iter()
+        .zip(schemes.iter())
+        .map(|(id, scheme)| KeyStore {
+            id: *id,
+            vss_scheme: scheme.clone(),
+            vss_coms: vss_coms.clone(),
+            vss_secret: schemes.iter().map(|s| s.share_to(*id)).sum(),
+        })
+        .collect()
+}
@@ -0,0 +1,60 @@
+/// Lagrange coefficient of member `id` for interpolating `f(0)` from the members `ids`.
+/// $$
+/// \lambda_i = \prod_{j \in ids, j \neq i}{\frac{x_j}{x_j - x_i}}
+/// $$
+///
+/// `f(0) = \sum_{i \in ids} \lambda_i \cdot f(i)`, which also holds "in the exponent",
+/// i.e. `G * f(0) = \sum_{i \in ids} (G * f(i)) * \lambda_i`.
+pub fn lagrange_coef(id: usize, ids: &[usize]) -> Scalar {
+    assert!(ids.contains(&id), "id {} is not among the members", id);
+    let x_i = Scalar::from(id as u64);
+    let mut num = Scalar::ONE;
+    let mut den = Scalar::ONE;
+    for j in ids.iter() {
+        if *j == id {
+            continue;
+        }
+        let x_j = Scalar::from(*j as u64);
+        num *= x_j;
+        den *= x_j - x_i;
+    }
+    let den_inv = den.invert();
+    assert!(
+        bool::from(den_inv.is_some()),
+        "duplicate ids among the members"
+    );
+    num * den_inv.unwrap()
+}
+
+/// Lagrange coefficients of all members `ids`, in the same order, with one scalar inversion.
+/// Prefer it to calling `lagrange_coef` for each member. Panics on duplicate ids.
+pub fn lagrange_coefs(ids: &[usize]) -> Vec<Scalar> {
+    shamir_secret_sharing::lagrange_coefs(ids, &ScalarField)
+}
+
+use k256::Scalar;
+use shamir_secret_sharing::ScalarField;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shamir_secret_sharing::share_secret;
+
+    #[test]
+    fn test_lagrange_coef() {
+        let secret = Scalar::from(1145141919810u64);
+        let shares = share_secret(&secret, 3, 5, &ScalarField);
+
+        let ids: Vec<usize> = vec![2, 4, 5];
+        let mut est = Scalar::ZERO;
+        for id in ids.iter() {
+            est += shares[id - 1].val * lagrange_coef(*id, &ids);
+        }
+        assert_eq!(est, secret);
+
+        let coefs = lagrange_coefs(&ids);
+        for (id, coef) in ids.iter().zip(coefs.iter()) {
+            assert_eq!(*coef, lagrange_coef(*id, &ids));
+        }
+    }
+}
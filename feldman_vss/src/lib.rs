@@ -1,9 +1,29 @@
 mod util;
-use num_traits::Euclid;
 pub use util::*;
-use util::{hash::DiyHash, interop::BigIntToScalar};
+use util::hash::DiyHash;
 mod keystore;
 pub use keystore::*;
+mod lagrange;
+pub use lagrange::*;
+mod transcript;
+pub use transcript::*;
+mod dleq;
+pub use dleq::*;
+mod ecdh;
+pub use ecdh::*;
+mod dlog;
+pub use dlog::*;
+mod elgamal;
+pub use elgamal::*;
+mod paillier;
+pub use paillier::*;
+mod sign;
+pub use sign::*;
+mod repair;
+pub use repair::*;
+mod hierarchical;
+pub use hierarchical::*;
+mod conversion;
 
 #[derive(Clone, Debug, Deref, DerefMut, Deserialize, Serialize)]
 pub struct VssCommitment(Vec<AffinePoint>);
@@ -11,7 +31,7 @@ pub struct VssCommitment(Vec<AffinePoint>);
 #[derive(Clone, Debug, Deref, DerefMut, Deserialize, Serialize)]
 pub struct VssLocalScheme {
     #[deref]
-    poly: Vec<BigInt>, // len == t, poly[0] == x
+    poly: Vec<Scalar>, // len == t, poly[0] == x
 }
 
 impl VssCommitment {
@@ -23,37 +43,44 @@ impl VssCommitment {
     pub fn prepare_to_check_vss_com(
         &self,            // vss commitment previously received from `id`
         id: usize,        // participant id
-        polyval: &BigInt, // value of polynomial computed at and received from `id`
+        polyval: &Scalar, // value of polynomial computed at and received from `id`
     ) -> (String, String) {
-        let x = BigInt::from(id).to_scalar();
-
-        let mut poly_com = ProjectivePoint::IDENTITY;
-        for coef_com in self.iter().rev() {
-            poly_com = (poly_com * &x) + coef_com;
-        }
-
-        let polyval_com = ProjectivePoint::GENERATOR * &polyval.to_scalar();
+        let poly_com = self.eval_at(id);
+        let polyval_com = ProjectivePoint::GENERATOR * polyval;
 
         let left = poly_com.diy_hash();
         let right = polyval_com.diy_hash();
         (left, right)
     }
+
+    /// Evaluate the committed polynomial at `id` in the exponent, i.e. `G * f(id)`.
+    pub fn eval_at(&self, id: usize) -> ProjectivePoint {
+        let x = Scalar::from(id as u64);
+
+        let mut poly_com = ProjectivePoint::IDENTITY;
+        for coef_com in self.iter().rev() {
+            poly_com = (poly_com * x) + coef_com;
+        }
+        poly_com
+    }
 }
 
 impl VssLocalScheme {
-    pub fn new_from_poly(poly: Vec<BigInt>) -> Self {
+    pub fn new_from_poly(poly: Vec<Scalar>) -> Self {
         Self { poly }
     }
 
     pub fn new(
         t: usize, // threshold or quorum. Minimum number of shards to reconstruct the secret.
     ) -> Self {
-        let mut rng = rand::thread_rng();
-        let one = const_1();
-        let order = const_secp256k1_order();
+        Self::new_with_rng(t, &mut rand::thread_rng())
+    }
+
+    /// Same as `new`, drawing the coefficients from `rng`.
+    pub fn new_with_rng<R: CryptoRng + RngCore>(t: usize, rng: &mut R) -> Self {
         let mut poly = Vec::new();
         for _ in 0..t {
-            let coef = rng.gen_bigint_range(&one, &order);
+            let coef = ScalarField.random_nonzero(rng);
             poly.push(coef);
         }
         Self { poly }
@@ -73,33 +100,27 @@ impl VssLocalScheme {
     /// and solving the secret from the public counterpart is mathematically hard.
     #[allow(non_snake_case)]
     pub fn commit(&self) -> VssCommitment {
-        use crate::util::interop::*;
-
         let mut com = VssCommitment(Vec::new());
         let G = ProjectivePoint::GENERATOR;
         for coef in self.poly.iter() {
-            let coef_com = G * &coef.to_scalar();
+            let coef_com = G * coef;
             com.push(coef_com.to_affine());
         }
         com
     }
 
     /// Share the secret to a participant.
-    pub fn share_to(&self, id: usize) -> BigInt {
-        use shamir_secret_sharing::eval_polynomial;
-
-        let id = BigInt::from(id);
-        let order = const_secp256k1_order();
-        let eval = eval_polynomial(&self.poly, &id, &order);
-        eval
+    pub fn share_to(&self, id: usize) -> Scalar {
+        let id = Scalar::from(id as u64);
+        eval_polynomial(&self.poly, &id, &ScalarField)
     }
 }
 
 use derive_more::{Deref, DerefMut};
-use k256::{AffinePoint, ProjectivePoint};
-use modulo_arithmetic::prelude::{const_1, const_secp256k1_order};
-use num_bigint::{BigInt, RandBigInt};
+use k256::{AffinePoint, ProjectivePoint, Scalar};
+use rand::{CryptoRng, RngCore};
 use serde::{Deserialize, Serialize};
+use shamir_secret_sharing::{eval_polynomial, FiniteField, ScalarField};
 
 #[cfg(test)]
 mod tests {
@@ -120,4 +141,14 @@ mod tests {
             assert_eq!(poly_com, polyval_com, "failed at id={}", id);
         }
     }
+
+    #[test]
+    fn test_share_seeded() {
+        use rand::{rngs::StdRng, SeedableRng};
+        let vss1 = VssLocalScheme::new_with_rng(3, &mut StdRng::seed_from_u64(42));
+        let vss2 = VssLocalScheme::new_with_rng(3, &mut StdRng::seed_from_u64(42));
+        let vss3 = VssLocalScheme::new_with_rng(3, &mut StdRng::seed_from_u64(43));
+        assert_eq!(vss1.share_to(1), vss2.share_to(1));
+        assert_ne!(vss1.share_to(1), vss3.share_to(1));
+    }
 }
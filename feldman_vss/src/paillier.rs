@@ -0,0 +1,237 @@
+//! Paillier encryption, additively homomorphic over `Z_n`, e.g. for MtA in threshold ECDSA.
+//!
+//! With `n = p * q` and `g = n + 1`:
+//! $$
+//! Enc(m; r) = (1 + n)^m \cdot r^n = (1 + m n) \cdot r^n \mod n^2
+//! $$
+//! so `Enc(m_1) * Enc(m_2)` encrypts `m_1 + m_2`, and `Enc(m)^k` encrypts `k * m`.
+//! Decryption works modulo `p^2` and `q^2` separately, with constant-time exponentiation
+//! by the secret `p - 1` and `q - 1`, then recombines by Garner's formula.
+//!
+//! `p` and `q` are safe primes of equal length, so `gcd(n, (p-1)(q-1)) == 1`.
+//! Use at least 2048-bit moduli in practice.
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PaillierPublicKey {
+    pub n: BigInt,
+}
+
+/// Serialized as `p` and `q` only; the rest is recomputed on load.
+/// No `Debug`, so that the primes do not end up in logs.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(from = "PaillierPrimes", into = "PaillierPrimes")]
+pub struct PaillierSecretKey {
+    p: BigInt,
+    q: BigInt,
+    p2: MontgomeryCtx, // modulo p^2
+    q2: MontgomeryCtx, // modulo q^2
+    h_p: BigInt,       // (-q)^{-1} mod p
+    h_q: BigInt,       // (-p)^{-1} mod q
+    q_inv_p: BigInt,   // q^{-1} mod p
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+struct PaillierPrimes {
+    p: BigInt,
+    q: BigInt,
+}
+
+/// `c` in `Z_{n^2}^*`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PaillierCiphertext(pub BigInt);
+
+impl PaillierSecretKey {
+    /// Random key with a `bits`-bit modulus, the product of two safe primes.
+    pub fn new(bits: u64) -> Self {
+        Self::new_with_rng(bits, &mut rand::thread_rng())
+    }
+
+    /// Same as `new`, drawing the primes from `rng`.
+    pub fn new_with_rng<R: CryptoRng + RngCore>(bits: u64, rng: &mut R) -> Self {
+        assert!(
+            bits >= 16 && bits.is_multiple_of(2),
+            "need an even bit length >= 16"
+        );
+        let p = random_safe_prime_with_rng(bits / 2, rng);
+        let mut q = random_safe_prime_with_rng(bits / 2, rng);
+        while q == p {
+            q = random_safe_prime_with_rng(bits / 2, rng);
+        }
+        Self::from(PaillierPrimes { p, q })
+    }
+
+    pub fn pk(&self) -> PaillierPublicKey {
+        PaillierPublicKey {
+            n: &self.p * &self.q,
+        }
+    }
+
+    /// The plaintext in `[0, n)`.
+    pub fn decrypt(&self, c: &PaillierCiphertext) -> BigInt {
+        let m_p = Self::decrypt_mod(&c.0, &self.p, &self.p2, &self.h_p);
+        let m_q = Self::decrypt_mod(&c.0, &self.q, &self.q2, &self.h_q);
+        let h = ((m_p - &m_q) * &self.q_inv_p).rem_euclid(&self.p);
+        m_q + &self.q * h
+    }
+
+    /// `m mod p`, from `c^(p-1) == 1 + (p-1) m n (mod p^2)`.
+    ///
+    /// With `L_p(x) = (x - 1) / p`, we have `L_p(c^(p-1) mod p^2) == (p-1) m q == -m q (mod p)`,
+    /// so `m == L_p(c^(p-1) mod p^2) \cdot (-q)^{-1} (mod p)`, where `h == (-q)^{-1} mod p`.
+    fn decrypt_mod(c: &BigInt, p: &BigInt, p2: &MontgomeryCtx, h: &BigInt) -> BigInt {
+        let x = p2.pow_ct(&p2.to_montgomery(c), &(p - 1u8), p.bits());
+        let l = (p2.from_montgomery(&x) - 1u8) / p;
+        (l * h).rem_euclid(p)
+    }
+}
+
+impl From<PaillierPrimes> for PaillierSecretKey {
+    fn from(PaillierPrimes { p, q }: PaillierPrimes) -> Self {
+        Self {
+            p2: MontgomeryCtx::new(&(&p * &p)),
+            q2: MontgomeryCtx::new(&(&q * &q)),
+            h_p: modinv_ct(&(-&q).rem_euclid(&p), &p),
+            h_q: modinv_ct(&(-&p).rem_euclid(&q), &q),
+            q_inv_p: modinv_ct(&q, &p),
+            p,
+            q,
+        }
+    }
+}
+
+impl From<PaillierSecretKey> for PaillierPrimes {
+    fn from(sk: PaillierSecretKey) -> Self {
+        Self { p: sk.p, q: sk.q }
+    }
+}
+
+impl PartialEq for PaillierSecretKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.p == other.p && self.q == other.q
+    }
+}
+
+impl Eq for PaillierSecretKey {}
+
+impl PaillierPublicKey {
+    pub fn n_squared(&self) -> BigInt {
+        &self.n * &self.n
+    }
+
+    /// Encrypt `m`, reduced into `[0, n)`.
+    pub fn encrypt(&self, m: &BigInt) -> PaillierCiphertext {
+        self.encrypt_with_rng(m, &mut rand::thread_rng())
+    }
+
+    /// Same as `encrypt`, drawing the nonce from `rng`.
+    pub fn encrypt_with_rng<R: CryptoRng + RngCore>(
+        &self,
+        m: &BigInt,
+        rng: &mut R,
+    ) -> PaillierCiphertext {
+        self.encrypt_with_nonce(m, &self.random_nonce(rng))
+    }
+
+    /// `Enc(m; r)`, for protocols that later prove knowledge of `r`.
+    pub fn encrypt_with_nonce(&self, m: &BigInt, r: &BigInt) -> PaillierCiphertext {
+        let n2 = self.n_squared();
+        let gm = (const_1() + m.rem_euclid(&self.n) * &self.n).rem_euclid(&n2);
+        PaillierCiphertext((gm * modpow(r, &self.n, &n2)).rem_euclid(&n2))
+    }
+
+    /// Uniformly random `r` in `Z_n^*`.
+    pub fn random_nonce<R: CryptoRng + RngCore>(&self, rng: &mut R) -> BigInt {
+        loop {
+            let r = rng.gen_bigint_range(&const_1(), &self.n);
+            if algo_extended_euclidean(&r, &self.n).gcd.is_one() {
+                return r;
+            }
+        }
+    }
+
+    /// `Enc(m_1 + m_2)` from `Enc(m_1)` and `Enc(m_2)`.
+    pub fn add(&self, c1: &PaillierCiphertext, c2: &PaillierCiphertext) -> PaillierCiphertext {
+        PaillierCiphertext((&c1.0 * &c2.0).rem_euclid(&self.n_squared()))
+    }
+
+    /// `Enc(m + k)` from `Enc(m)` and a plaintext `k`, with the same nonce.
+    pub fn add_plain(&self, c: &PaillierCiphertext, k: &BigInt) -> PaillierCiphertext {
+        self.add(c, &self.encrypt_with_nonce(k, &const_1()))
+    }
+
+    /// `Enc(k * m)` from `Enc(m)` and a plaintext `k`, which may be negative.
+    pub fn mul_plain(&self, c: &PaillierCiphertext, k: &BigInt) -> PaillierCiphertext {
+        PaillierCiphertext(modpow(&c.0, k, &self.n_squared()))
+    }
+
+    /// Same plaintext with a fresh nonce, so that the result is unlinkable to `c`.
+    pub fn rerandomize_with_rng<R: CryptoRng + RngCore>(
+        &self,
+        c: &PaillierCiphertext,
+        rng: &mut R,
+    ) -> PaillierCiphertext {
+        self.add(c, &self.encrypt_with_rng(&const_0(), rng))
+    }
+}
+
+use modulo_arithmetic::prelude::*;
+use modulo_arithmetic::{
+    algo_extended_euclidean, modinv_ct, modpow, random_safe_prime_with_rng, MontgomeryCtx,
+};
+use num_bigint::{BigInt, RandBigInt};
+use num_traits::{Euclid, One};
+use rand::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn test_paillier() {
+        let mut rng = StdRng::seed_from_u64(1145141919810);
+        let sk = PaillierSecretKey::new_with_rng(128, &mut rng);
+        let pk = sk.pk();
+        assert_eq!(pk.n.bits(), 128);
+
+        let a = BigInt::from(1145141919810893u64);
+        let b = &pk.n - 114514;
+        let ca = pk.encrypt_with_rng(&a, &mut rng);
+        let cb = pk.encrypt_with_rng(&b, &mut rng);
+        assert_eq!(sk.decrypt(&ca), a);
+        assert_eq!(sk.decrypt(&cb), b);
+        assert_ne!(ca, pk.encrypt_with_rng(&a, &mut rng)); // randomized
+
+        assert_eq!(sk.decrypt(&pk.add(&ca, &cb)), (&a + &b) % &pk.n);
+        assert_eq!(sk.decrypt(&pk.add_plain(&ca, &b)), (&a + &b) % &pk.n);
+        let k = BigInt::from(-1919);
+        assert_eq!(
+            sk.decrypt(&pk.mul_plain(&ca, &k)),
+            (&a * &k).rem_euclid(&pk.n)
+        );
+        let cr = pk.rerandomize_with_rng(&ca, &mut rng);
+        assert_ne!(cr, ca);
+        assert_eq!(sk.decrypt(&cr), a);
+
+        let r = pk.random_nonce(&mut rng);
+        assert_eq!(pk.encrypt_with_nonce(&a, &r), pk.encrypt_with_nonce(&a, &r));
+    }
+
+    #[test]
+    fn test_paillier_serde() {
+        let mut rng = StdRng::seed_from_u64(1919810);
+        let sk = PaillierSecretKey::new_with_rng(64, &mut rng);
+        let pk = sk.pk();
+        let c = pk.encrypt_with_rng(&BigInt::from(42), &mut rng);
+
+        fn roundtrip<T: Serialize + for<'de> Deserialize<'de>>(x: &T) -> T {
+            let buf = serde_pickle::to_vec(x, serde_pickle::SerOptions::default()).unwrap();
+            serde_pickle::from_slice(&buf, serde_pickle::DeOptions::default()).unwrap()
+        }
+        let (sk2, pk2, c2) = (roundtrip(&sk), roundtrip(&pk), roundtrip(&c));
+        assert!(sk2 == sk);
+        assert_eq!((pk2, c2.clone()), (pk, c));
+        assert_eq!(sk2.decrypt(&c2), BigInt::from(42));
+    }
+}
@@ -0,0 +1,78 @@
+//! Repair the keystore of a member who lost it,
+//! using `shamir_secret_sharing`'s repair protocol over the main secret's shares.
+//!
+//! `t` helpers jointly compute `x_j` for the lost member `j`.
+//! Nobody but `j` learns `x_j`, and nobody learns the main secret.
+//! `j` checks the result against the verification share `G * x_j` from the public VSS commitments.
+
+/// Step 1, by each helper. Pieces of the masked share, keyed by the receiving helper.
+pub fn repair_pieces_for(
+    keystore: &KeyStore,
+    helpers: &[usize],
+    lost_id: usize,
+) -> HashMap<usize, Scalar> {
+    repair_pieces_for_with_rng(keystore, helpers, lost_id, &mut rand::thread_rng())
+}
+
+/// Same as `repair_pieces_for`, drawing the masks from `rng`.
+pub fn repair_pieces_for_with_rng<R: CryptoRng + RngCore>(
+    keystore: &KeyStore,
+    helpers: &[usize],
+    lost_id: usize,
+    rng: &mut R,
+) -> HashMap<usize, Scalar> {
+    assert!(
+        helpers.len() >= keystore.t(),
+        "Need at least {} helpers, got {}",
+        keystore.t(),
+        helpers.len()
+    );
+    assert!(helpers.contains(&keystore.id));
+    let share = ShamirShare {
+        id: keystore.id,
+        val: keystore.vss_secret,
+    };
+    repair_pieces_with_rng(&share, helpers, lost_id, &ScalarField, rng)
+}
+
+/// Step 2, by each helper. Sum of the pieces received from all helpers, to be sent to the lost member.
+pub fn repair_sum_pieces(pieces: &[Scalar]) -> Scalar {
+    repair_sum(pieces, &ScalarField)
+}
+
+impl KeyStore {
+    /// Step 3, by the lost member. Rebuild the keystore from the sums sent by the helpers
+    /// and the public VSS commitments, which any member can provide.
+    ///
+    /// The member's own dealing polynomial is lost with its device and not needed after DKG,
+    /// so `vss_scheme` of the repaired keystore is empty.
+    pub fn repaired(
+        lost_id: usize,
+        vss_coms: HashMap<usize, VssCommitment>,
+        sigmas: &[Scalar],
+    ) -> Self {
+        let share = repair_finish(sigmas, lost_id, &ScalarField);
+        let keystore = KeyStore {
+            id: lost_id,
+            vss_scheme: VssLocalScheme::new_from_poly(vec![]),
+            vss_coms,
+            vss_secret: share.val,
+        };
+        assert_eq!(
+            ProjectivePoint::GENERATOR * keystore.vss_secret,
+            keystore.pk_share(lost_id),
+            "Repaired share of {} does not match its verification share",
+            lost_id
+        );
+        keystore
+    }
+}
+
+use std::collections::HashMap;
+
+use crate::{KeyStore, VssCommitment, VssLocalScheme};
+use k256::{ProjectivePoint, Scalar};
+use rand::{CryptoRng, RngCore};
+use shamir_secret_sharing::{
+    repair_finish, repair_pieces_with_rng, repair_sum, ScalarField, ShamirShare,
+};
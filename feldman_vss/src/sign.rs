@@ -0,0 +1,396 @@
+//! Threshold Schnorr signing with identifiable abort (two rounds, FROST-like).
+//!
+//! Round 1. Each signer `i` samples nonces `(d_i, e_i)` and publishes
+//! `(D_i, E_i) = (G * d_i, G * e_i)`.
+//!
+//! Round 2. With binding factors `\rho_i = H(i, msg, {D_j, E_j})`,
+//! $$
+//! R = \sum_i (D_i + E_i * \rho_i), \quad c = H(R, pk, msg)
+//! $$
+//! and each signer publishes `z_i = d_i + e_i * \rho_i + \lambda_i * x_i * c`.
+//!
+//! Every `z_i` is checked against the verification share `X_i = G * x_i`:
+//! $$
+//! G * z_i == D_i + E_i * \rho_i + X_i * (\lambda_i * c)
+//! $$
+//! so a bad signer is named instead of yielding a mismatched signature.
+
+/// Secret nonces of round 1. Must be used for exactly one signature,
+/// hence neither `Clone` nor a `Debug` that prints them.
+pub struct SigningNonces {
+    d: Scalar,
+    e: Scalar,
+}
+
+/// Public commitment to [`SigningNonces`], broadcast in round 1.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SigningCommitment {
+    pub id: usize,
+    pub d: AffinePoint, // G * d_i
+    pub e: AffinePoint, // G * e_i
+}
+
+/// `z_i` of round 2.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PartialSignature {
+    pub id: usize,
+    pub z: Scalar,
+}
+
+/// Schnorr signature `(R, z)` with `G * z == R + pk * H(R, pk, msg)`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SchnorrSignature {
+    pub r: AffinePoint,
+    pub z: Scalar,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SignError {
+    /// Fewer signers than the threshold.
+    NotEnoughSigners { need: usize, got: usize },
+    /// A partial signature comes from an id without a round 1 commitment.
+    UnknownSigners(Vec<usize>),
+    /// These ids occur more than once among the commitments or the partial signatures.
+    DuplicateSigners(Vec<usize>),
+    /// Partial signatures of these ids fail verification against their verification shares.
+    /// Leave them out and retry.
+    Misbehaving(Vec<usize>),
+    /// Every partial signature verifies, but their sum does not,
+    /// i.e. the keystore is inconsistent with the signers' ones.
+    InvalidSignature,
+}
+
+impl SigningNonces {
+    pub fn new(id: usize) -> (Self, SigningCommitment) {
+        Self::new_with_rng(id, &mut rand::thread_rng())
+    }
+
+    /// Same as `new`, drawing the nonces from `rng`.
+    /// A seeded `rng` must never be reused across signatures.
+    pub fn new_with_rng<R: CryptoRng + RngCore>(
+        id: usize,
+        rng: &mut R,
+    ) -> (Self, SigningCommitment) {
+        let nonces = Self {
+            d: Scalar::random(&mut *rng),
+            e: Scalar::random(&mut *rng),
+        };
+        let com = SigningCommitment {
+            id,
+            d: (ProjectivePoint::GENERATOR * nonces.d).to_affine(),
+            e: (ProjectivePoint::GENERATOR * nonces.e).to_affine(),
+        };
+        (nonces, com)
+    }
+}
+
+impl std::fmt::Debug for SigningNonces {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SigningNonces").finish_non_exhaustive()
+    }
+}
+
+/// Produce `z_i` for the member owning `keystore`.
+pub fn sign_partial(
+    keystore: &KeyStore,
+    nonces: SigningNonces,
+    coms: &[SigningCommitment],
+    msg: &[u8],
+) -> PartialSignature {
+    let session = SigningSession::new(keystore, coms, msg);
+    let id = keystore.id;
+    let rho = session.binding_factor(id);
+    let w_i = keystore.additive_share(&session.ids);
+    let z = nonces.d + nonces.e * rho + w_i * session.c;
+    PartialSignature { id, z }
+}
+
+/// Check `z_i` against the verification share of its sender.
+pub fn verify_partial(
+    keystore: &KeyStore,
+    coms: &[SigningCommitment],
+    msg: &[u8],
+    psig: &PartialSignature,
+) -> bool {
+    SigningSession::new(keystore, coms, msg).verify(keystore, psig)
+}
+
+/// Aggregate partial signatures, naming the signers whose `z_i` is invalid.
+pub fn aggregate_signature(
+    keystore: &KeyStore,
+    coms: &[SigningCommitment],
+    msg: &[u8],
+    psigs: &[PartialSignature],
+) -> Result<SchnorrSignature, SignError> {
+    let dup = duplicates(coms.iter().map(|com| com.id));
+    if !dup.is_empty() {
+        return Err(SignError::DuplicateSigners(dup));
+    }
+    if coms.len() < keystore.t() {
+        return Err(SignError::NotEnoughSigners {
+            need: keystore.t(),
+            got: coms.len(),
+        });
+    }
+    let session = SigningSession::new(keystore, coms, msg);
+
+    let mut unknown: Vec<usize> = psigs
+        .iter()
+        .map(|psig| psig.id)
+        .filter(|id| !session.ids.contains(id))
+        .collect();
+    if !unknown.is_empty() {
+        unknown.sort();
+        return Err(SignError::UnknownSigners(unknown));
+    }
+    let dup = duplicates(psigs.iter().map(|psig| psig.id));
+    if !dup.is_empty() {
+        return Err(SignError::DuplicateSigners(dup));
+    }
+
+    // Exactly one verified partial signature per signer goes into `z`.
+    let mut z = Scalar::ZERO;
+    let mut bad: Vec<usize> = Vec::new();
+    for id in session.ids.iter() {
+        match psigs.iter().find(|psig| psig.id == *id) {
+            Some(psig) if session.verify(keystore, psig) => z += psig.z,
+            _ => bad.push(*id), // missing counts as misbehaving
+        }
+    }
+    if !bad.is_empty() {
+        bad.sort();
+        return Err(SignError::Misbehaving(bad));
+    }
+
+    let sig = SchnorrSignature {
+        r: session.r.to_affine(),
+        z,
+    };
+    if !sig.verify(&keystore.pk(), msg) {
+        return Err(SignError::InvalidSignature);
+    }
+    Ok(sig)
+}
+
+/// Ids occurring more than once, ascending.
+fn duplicates(ids: impl Iterator<Item = usize>) -> Vec<usize> {
+    let mut seen: HashSet<usize> = HashSet::new();
+    let mut dup: Vec<usize> = ids.filter(|id| !seen.insert(*id)).collect();
+    dup.sort();
+    dup.dedup();
+    dup
+}
+
+impl SchnorrSignature {
+    pub fn verify(&self, pk: &ProjectivePoint, msg: &[u8]) -> bool {
+        let r = ProjectivePoint::from(self.r);
+        let c = challenge(&r, pk, msg);
+        ProjectivePoint::GENERATOR * self.z == r + pk * &c
+    }
+}
+
+/// Everything derived from the round 1 commitments and the message.
+struct SigningSession<'a> {
+    coms: Vec<&'a SigningCommitment>, // in ascending id order
+    ids: Vec<usize>,
+    msg: &'a [u8],
+    r: ProjectivePoint,
+    c: Scalar,
+}
+
+impl<'a> SigningSession<'a> {
+    fn new(keystore: &KeyStore, coms: &'a [SigningCommitment], msg: &'a [u8]) -> Self {
+        let ids: Vec<usize> = coms.iter().map(|com| com.id).collect();
+        let mut sorted: Vec<&SigningCommitment> = coms.iter().collect();
+        sorted.sort_by_key(|com| com.id);
+        let mut session = Self {
+            coms: sorted,
+            ids,
+            msg,
+            r: ProjectivePoint::IDENTITY,
+            c: Scalar::ZERO,
+        };
+        for com in coms.iter() {
+            let rho = session.binding_factor(com.id);
+            session.r += ProjectivePoint::from(com.d) + ProjectivePoint::from(com.e) * rho;
+        }
+        session.c = challenge(&session.r, &keystore.pk(), msg);
+        session
+    }
+
+    /// `\rho_i = H(i, msg, {D_j, E_j})`, where the commitments are taken in ascending id order.
+    fn binding_factor(&self, id: usize) -> Scalar {
+        let mut transcript = Transcript::new(b"feldman_vss/sign/rho");
+        transcript.append_u64(b"id", id as u64);
+        transcript.append_message(b"msg", self.msg);
+        for com in self.coms.iter() {
+            transcript.append_u64(b"com_id", com.id as u64);
+            transcript.append_point(b"D", &ProjectivePoint::from(com.d));
+            transcript.append_point(b"E", &ProjectivePoint::from(com.e));
+        }
+        transcript.challenge_scalar(b"rho")
+    }
+
+    fn verify(&self, keystore: &KeyStore, psig: &PartialSignature) -> bool {
+        let com = match self.coms.iter().find(|com| com.id == psig.id) {
+            Some(com) => com,
+            None => return false,
+        };
+        let rho = self.binding_factor(psig.id);
+        let r_i = ProjectivePoint::from(com.d) + ProjectivePoint::from(com.e) * rho;
+        let left = ProjectivePoint::GENERATOR * psig.z;
+        let right = r_i + keystore.additive_pk_share(psig.id, &self.ids) * self.c;
+        left == right
+    }
+}
+
+/// `c = H(R, pk, msg)`
+fn challenge(r: &ProjectivePoint, pk: &ProjectivePoint, msg: &[u8]) -> Scalar {
+    let mut transcript = Transcript::new(b"feldman_vss/sign/challenge");
+    transcript.append_point(b"R", r);
+    transcript.append_point(b"pk", pk);
+    transcript.append_message(b"msg", msg);
+    transcript.challenge_scalar(b"c")
+}
+
+impl std::fmt::Display for SignError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SignError::NotEnoughSigners { need, got } => {
+                write!(f, "need at least {} signers, got {}", need, got)
+            }
+            SignError::UnknownSigners(ids) => {
+                write!(f, "partial signatures without commitment from {:?}", ids)
+            }
+            SignError::DuplicateSigners(ids) => write!(f, "duplicate signers {:?}", ids),
+            SignError::Misbehaving(ids) => write!(f, "invalid partial signatures from {:?}", ids),
+            SignError::InvalidSignature => write!(f, "aggregated signature does not verify"),
+        }
+    }
+}
+
+impl std::error::Error for SignError {}
+
+use std::collections::HashSet;
+
+use crate::{KeyStore, Transcript};
+use k256::{elliptic_curve::Field, AffinePoint, ProjectivePoint, Scalar};
+use rand::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keystore::mock_dkg;
+
+    fn sign_round_1(signers: &[&KeyStore]) -> (Vec<SigningNonces>, Vec<SigningCommitment>) {
+        signers.iter().map(|ks| SigningNonces::new(ks.id)).unzip()
+    }
+
+    #[test]
+    fn test_sign() {
+        let keystores = mock_dkg(3, &[1, 2, 3, 4, 5]);
+        let signers = [&keystores[0], &keystores[2], &keystores[4]];
+        let msg = b"hello world";
+
+        let (nonces, coms) = sign_round_1(&signers);
+        assert_eq!(format!("{:?}", nonces[0]), "SigningNonces { .. }");
+        let psigs: Vec<PartialSignature> = signers
+            .iter()
+            .zip(nonces)
+            .map(|(ks, nonces)| sign_partial(ks, nonces, &coms, msg))
+            .collect();
+        for psig in psigs.iter() {
+            assert!(verify_partial(signers[0], &coms, msg, psig));
+        }
+        let sig = aggregate_signature(signers[0], &coms, msg, &psigs).unwrap();
+        assert!(sig.verify(&keystores[0].pk(), msg));
+        assert!(!sig.verify(&keystores[0].pk(), b"another message"));
+    }
+
+    #[test]
+    fn test_sign_seeded() {
+        use rand::{rngs::StdRng, SeedableRng};
+        let keystores = mock_dkg(2, &[1, 2, 3]);
+        let signers = [&keystores[0], &keystores[2]];
+        let msg = b"hello world";
+
+        let sign = |seed: u64| {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let (nonces, coms): (Vec<SigningNonces>, Vec<SigningCommitment>) = signers
+                .iter()
+                .map(|ks| SigningNonces::new_with_rng(ks.id, &mut rng))
+                .unzip();
+            let psigs: Vec<PartialSignature> = signers
+                .iter()
+                .zip(nonces)
+                .map(|(ks, nonces)| sign_partial(ks, nonces, &coms, msg))
+                .collect();
+            aggregate_signature(signers[0], &coms, msg, &psigs).unwrap()
+        };
+        let sig = sign(42);
+        assert_eq!(sig, sign(42));
+        assert_ne!(sig, sign(43));
+        assert!(sign(43).verify(&keystores[0].pk(), msg));
+    }
+
+    #[test]
+    fn test_identifiable_abort() {
+        let keystores = mock_dkg(3, &[1, 2, 3, 4, 5]);
+        let signers = [&keystores[1], &keystores[2], &keystores[3], &keystores[4]];
+        let msg = b"hello world";
+
+        let (nonces, coms) = sign_round_1(&signers);
+        let mut psigs: Vec<PartialSignature> = signers
+            .iter()
+            .zip(nonces)
+            .map(|(ks, nonces)| sign_partial(ks, nonces, &coms, msg))
+            .collect();
+        psigs[1].z += Scalar::ONE; // id 3 cheats
+        psigs[3].z = Scalar::ZERO; // id 5 cheats
+
+        let res = aggregate_signature(signers[0], &coms, msg, &psigs);
+        assert_eq!(res, Err(SignError::Misbehaving(vec![3, 5])));
+
+        // A missing partial signature is blamed too.
+        let res = aggregate_signature(signers[0], &coms, msg, &psigs[..1]);
+        assert_eq!(res, Err(SignError::Misbehaving(vec![3, 4, 5])));
+
+        // Retry without the cheaters.
+        let signers = [&keystores[0], &keystores[1], &keystores[3]];
+        let (nonces, coms) = sign_round_1(&signers);
+        let psigs: Vec<PartialSignature> = signers
+            .iter()
+            .zip(nonces)
+            .map(|(ks, nonces)| sign_partial(ks, nonces, &coms, msg))
+            .collect();
+        let sig = aggregate_signature(signers[0], &coms, msg, &psigs).unwrap();
+        assert!(sig.verify(&keystores[0].pk(), msg));
+    }
+
+    #[test]
+    fn test_duplicate_signers() {
+        let keystores = mock_dkg(2, &[1, 2, 3]);
+        let signers = [&keystores[0], &keystores[1]];
+        let msg = b"hello world";
+
+        let (nonces, coms) = sign_round_1(&signers);
+        let mut psigs: Vec<PartialSignature> = signers
+            .iter()
+            .zip(nonces)
+            .map(|(ks, nonces)| sign_partial(ks, nonces, &coms, msg))
+            .collect();
+
+        // A valid partial signature sent twice must not be added twice.
+        psigs.push(psigs[1].clone());
+        let res = aggregate_signature(signers[0], &coms, msg, &psigs);
+        assert_eq!(res, Err(SignError::DuplicateSigners(vec![2])));
+        psigs.pop();
+        assert!(aggregate_signature(signers[0], &coms, msg, &psigs).is_ok());
+
+        let mut coms = coms;
+        coms.push(coms[0].clone());
+        let res = aggregate_signature(signers[0], &coms, msg, &psigs);
+        assert_eq!(res, Err(SignError::DuplicateSigners(vec![1])));
+    }
+}
@@ -0,0 +1,116 @@
+//! Fiat-Shamir transcript, in the spirit of Merlin.
+//!
+//! A proof appends its statement and commitments to a transcript, each under a label,
+//! and squeezes challenges from everything appended so far.
+//! Prover and verifier replay the same appends, so they derive the same challenges.
+//!
+//! Every item is absorbed as `op || len(label) || label || len(data) || data`,
+//! so no two different sequences of appends hash alike,
+//! and the domain given to `Transcript::new` separates protocols.
+//! Each challenge is absorbed back, so later challenges depend on earlier ones.
+
+#[derive(Clone, Debug)]
+pub struct Transcript {
+    hasher: Blake2b<U64>,
+}
+
+impl Transcript {
+    const OP_DOMAIN: u8 = 0;
+    const OP_APPEND: u8 = 1;
+    const OP_CHALLENGE: u8 = 2;
+
+    /// Empty transcript for the protocol `domain`, e.g. `b"feldman_vss/dleq"`.
+    pub fn new(domain: &'static [u8]) -> Self {
+        let mut transcript = Self {
+            hasher: Blake2b::<U64>::new(),
+        };
+        transcript.absorb(Self::OP_DOMAIN, b"domain", domain);
+        transcript
+    }
+
+    pub fn append_message(&mut self, label: &'static [u8], msg: &[u8]) {
+        self.absorb(Self::OP_APPEND, label, msg);
+    }
+
+    pub fn append_u64(&mut self, label: &'static [u8], x: u64) {
+        self.absorb(Self::OP_APPEND, label, &x.to_be_bytes());
+    }
+
+    /// Compressed SEC1 encoding, where the identity is a single zero byte.
+    pub fn append_point(&mut self, label: &'static [u8], point: &ProjectivePoint) {
+        self.absorb(
+            Self::OP_APPEND,
+            label,
+            point.to_encoded_point(true).as_bytes(),
+        );
+    }
+
+    /// 32 big-endian bytes.
+    pub fn append_scalar(&mut self, label: &'static [u8], scalar: &Scalar) {
+        self.absorb(Self::OP_APPEND, label, &scalar.to_bytes());
+    }
+
+    /// A challenge scalar, from 512 hash bits reduced modulo the curve order,
+    /// i.e. uniform up to a bias of about `2^-256`.
+    pub fn challenge_scalar(&mut self, label: &'static [u8]) -> Scalar {
+        self.absorb(Self::OP_CHALLENGE, label, &[]);
+        let output = self.hasher.clone().finalize();
+        self.absorb(Self::OP_CHALLENGE, label, &output);
+        <Scalar as Reduce<U512>>::reduce_bytes(&output)
+    }
+
+    fn absorb(&mut self, op: u8, label: &[u8], data: &[u8]) {
+        self.hasher.update([op]);
+        self.hasher.update((label.len() as u64).to_be_bytes());
+        self.hasher.update(label);
+        self.hasher.update((data.len() as u64).to_be_bytes());
+        self.hasher.update(data);
+    }
+}
+
+use blake2::{digest::consts::U64, Blake2b, Digest};
+use k256::{
+    elliptic_curve::{bigint::U512, ops::Reduce, sec1::ToEncodedPoint},
+    ProjectivePoint, Scalar,
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transcript() {
+        let point = ProjectivePoint::GENERATOR * Scalar::from(114514u64);
+        let make = || {
+            let mut t = Transcript::new(b"test");
+            t.append_point(b"P", &point);
+            t.append_scalar(b"s", &Scalar::from(1919810u64));
+            t.append_message(b"msg", b"hello");
+            t
+        };
+        let (mut t1, mut t2) = (make(), make());
+        let c1 = t1.challenge_scalar(b"c");
+        assert_eq!(c1, t2.challenge_scalar(b"c"));
+
+        // Later challenges differ from earlier ones, but still agree.
+        let c2 = t1.challenge_scalar(b"c");
+        assert_ne!(c1, c2);
+        assert_eq!(c2, t2.challenge_scalar(b"c"));
+
+        // Domain, labels and framing all matter.
+        let mut t = Transcript::new(b"other");
+        t.append_point(b"P", &point);
+        t.append_scalar(b"s", &Scalar::from(1919810u64));
+        t.append_message(b"msg", b"hello");
+        assert_ne!(t.challenge_scalar(b"c"), c1);
+
+        let mut t = make();
+        assert_ne!(t.challenge_scalar(b"d"), c1);
+
+        let mut t1 = Transcript::new(b"test");
+        t1.append_message(b"a", b"bc");
+        let mut t2 = Transcript::new(b"test");
+        t2.append_message(b"ab", b"c");
+        assert_ne!(t1.challenge_scalar(b"c"), t2.challenge_scalar(b"c"));
+    }
+}
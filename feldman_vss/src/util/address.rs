@@ -0,0 +1,218 @@
+//! Encodings of a public key, e.g. `KeyStore::pk()`, used by wallets.
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BtcNetwork {
+    Mainnet,
+    Testnet,
+    Regtest,
+}
+
+impl BtcNetwork {
+    fn hrp(&self) -> Hrp {
+        match self {
+            BtcNetwork::Mainnet => hrp::BC,
+            BtcNetwork::Testnet => hrp::TB,
+            BtcNetwork::Regtest => hrp::BCRT,
+        }
+    }
+}
+
+pub trait PkEncoding {
+    /// 33-byte SEC1 encoding, `02 || x` or `03 || x`, in hex.
+    fn to_sec1_compressed_hex(&self) -> String;
+
+    /// 65-byte SEC1 encoding, `04 || x || y`, in hex.
+    fn to_sec1_uncompressed_hex(&self) -> String;
+
+    /// 32-byte BIP340 x-only key, in hex, or `None` for the identity.
+    /// If `y` is odd, this is the key of the negated secret.
+    fn to_xonly_hex(&self) -> Option<String>;
+
+    /// BIP173 pay-to-witness-public-key-hash address (bech32), or `None` for the identity.
+    fn to_p2wpkh_address(&self, network: BtcNetwork) -> Option<String>;
+
+    /// BIP341 pay-to-taproot address (bech32m), key path only,
+    /// with the output key tweaked as BIP86 suggests.
+    /// `None` for the identity, which has no x-only form.
+    fn to_p2tr_address(&self, network: BtcNetwork) -> Option<String>;
+
+    /// EIP-55 mixed-case checksum address, or `None` for the identity.
+    fn to_eth_address(&self) -> Option<String>;
+}
+
+impl PkEncoding for ProjectivePoint {
+    fn to_sec1_compressed_hex(&self) -> String {
+        hex::encode(self.to_encoded_point(true).as_bytes())
+    }
+
+    fn to_sec1_uncompressed_hex(&self) -> String {
+        hex::encode(self.to_encoded_point(false).as_bytes())
+    }
+
+    fn to_xonly_hex(&self) -> Option<String> {
+        xonly(self).map(hex::encode)
+    }
+
+    fn to_p2wpkh_address(&self, network: BtcNetwork) -> Option<String> {
+        if self.is_identity().into() {
+            return None;
+        }
+        let sha = Sha256::digest(self.to_encoded_point(true).as_bytes());
+        let hash160 = Ripemd160::digest(sha);
+        Some(segwit::encode(network.hrp(), segwit::VERSION_0, &hash160).unwrap())
+    }
+
+    #[allow(non_snake_case)]
+    fn to_p2tr_address(&self, network: BtcNetwork) -> Option<String> {
+        // Lift to the point with even `y`, as BIP340 does.
+        let P = if bool::from(self.to_affine().y_is_odd()) {
+            -self
+        } else {
+            *self
+        };
+        let P_x = xonly(&P)?;
+        let t = tagged_hash(b"TapTweak", &P_x);
+        let t = Option::<Scalar>::from(Scalar::from_repr(t.into()))
+            .expect("TapTweak exceeds the curve order");
+        let Q = P + ProjectivePoint::GENERATOR * t;
+        Some(segwit::encode(network.hrp(), segwit::VERSION_1, &xonly(&Q)?).unwrap())
+    }
+
+    fn to_eth_address(&self) -> Option<String> {
+        if self.is_identity().into() {
+            return None;
+        }
+        let point = self.to_encoded_point(false);
+        let hash = Keccak256::digest(&point.as_bytes()[1..]);
+        let addr = hex::encode(&hash[12..]);
+
+        // EIP-55: uppercase the i-th hex digit if the i-th nibble of keccak(addr) is >= 8.
+        let checksum = Keccak256::digest(addr.as_bytes());
+        let mut res = String::from("0x");
+        for (i, ch) in addr.chars().enumerate() {
+            let nibble = (checksum[i / 2] >> (4 * (1 - i % 2))) & 0x0f;
+            if nibble >= 8 {
+                res.push(ch.to_ascii_uppercase());
+            } else {
+                res.push(ch);
+            }
+        }
+        Some(res)
+    }
+}
+
+/// `x` of a non-identity point. The identity encodes as the single byte `00`.
+fn xonly(point: &ProjectivePoint) -> Option<[u8; 32]> {
+    let point = point.to_encoded_point(true);
+    point.as_bytes()[1..].try_into().ok()
+}
+
+/// BIP340 tagged hash, `SHA256(SHA256(tag) || SHA256(tag) || msg)`.
+fn tagged_hash(tag: &[u8], msg: &[u8]) -> [u8; 32] {
+    let tag_hash = Sha256::digest(tag);
+    let mut hasher = Sha256::new();
+    hasher.update(tag_hash);
+    hasher.update(tag_hash);
+    hasher.update(msg);
+    hasher.finalize().into()
+}
+
+use bech32::{hrp, segwit, Hrp};
+use k256::{
+    elliptic_curve::{group::Group, point::AffineCoordinates, sec1::ToEncodedPoint, PrimeField},
+    ProjectivePoint, Scalar,
+};
+use ripemd::Ripemd160;
+use sha2::{Digest, Sha256};
+use sha3::Keccak256;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k256::{elliptic_curve::sec1::FromEncodedPoint, AffinePoint, EncodedPoint};
+
+    fn point_from_sk(sk_hex: &str) -> ProjectivePoint {
+        let sk: [u8; 32] = hex::decode(sk_hex).unwrap().try_into().unwrap();
+        let sk = Scalar::from_repr(sk.into()).unwrap();
+        ProjectivePoint::GENERATOR * sk
+    }
+
+    fn point_from_sec1(sec1_hex: &str) -> ProjectivePoint {
+        let bytes = hex::decode(sec1_hex).unwrap();
+        let point = EncodedPoint::from_bytes(bytes).unwrap();
+        AffinePoint::from_encoded_point(&point).unwrap().into()
+    }
+
+    #[test]
+    fn test_sec1() {
+        let g = ProjectivePoint::GENERATOR;
+        assert_eq!(
+            g.to_sec1_compressed_hex(),
+            "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798"
+        );
+        assert_eq!(
+            g.to_sec1_uncompressed_hex(),
+            "0479be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798\
+            483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8"
+        );
+        assert_eq!(
+            g.to_xonly_hex().unwrap(),
+            "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798"
+        );
+        assert_eq!((-g).to_xonly_hex(), g.to_xonly_hex());
+        assert_eq!(ProjectivePoint::IDENTITY.to_xonly_hex(), None);
+    }
+
+    #[test]
+    fn test_p2wpkh() {
+        // BIP173
+        let g = ProjectivePoint::GENERATOR;
+        assert_eq!(
+            g.to_p2wpkh_address(BtcNetwork::Mainnet).unwrap(),
+            "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"
+        );
+        assert_eq!(
+            g.to_p2wpkh_address(BtcNetwork::Testnet).unwrap(),
+            "tb1qw508d6qejxtdg4y5r3zarvary0c5xw7kxpjzsx"
+        );
+        assert_eq!(
+            ProjectivePoint::IDENTITY.to_p2wpkh_address(BtcNetwork::Mainnet),
+            None
+        );
+    }
+
+    #[test]
+    fn test_p2tr() {
+        // BIP86, first receiving address of account 0.
+        let pk =
+            point_from_sec1("02cc8a4bc64d897bddc5fbc2f670f7a8ba0b386779106cf1223c6fc5d7cd6fc115");
+        assert_eq!(
+            pk.to_p2tr_address(BtcNetwork::Mainnet).unwrap(),
+            "bc1p5cyxnuxmeuwuvkwfem96lqzszd02n6xdcjrs20cac6yqjjwudpxqkedrcr"
+        );
+        // Odd `y` gives the same address as its even counterpart.
+        assert_eq!(
+            (-pk).to_p2tr_address(BtcNetwork::Mainnet),
+            pk.to_p2tr_address(BtcNetwork::Mainnet)
+        );
+        assert_eq!(
+            ProjectivePoint::IDENTITY.to_p2tr_address(BtcNetwork::Mainnet),
+            None
+        );
+    }
+
+    #[test]
+    fn test_eth() {
+        let pk = point_from_sk("0000000000000000000000000000000000000000000000000000000000000001");
+        assert_eq!(
+            pk.to_eth_address().unwrap(),
+            "0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf"
+        );
+        let pk = point_from_sk("4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318");
+        assert_eq!(
+            pk.to_eth_address().unwrap(),
+            "0x2c7536E3605D9C16a7a3D7b1898e529396a65c23"
+        );
+        assert_eq!(ProjectivePoint::IDENTITY.to_eth_address(), None);
+    }
+}
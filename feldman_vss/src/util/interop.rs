@@ -26,15 +26,14 @@ impl BigIntToScalar for BigInt {
         let n = const_secp256k1_order();
         let a = self.rem_euclid(&n);
         let src_buf = a.to_bytes_be().1;
-        let dst_buf: [u8; 32];
-        if src_buf.len() > 32 {
+        let dst_buf: [u8; 32] = if src_buf.len() > 32 {
             let src_buf_truncated = &src_buf[src_buf.len() - 32..];
-            dst_buf = src_buf_truncated.try_into().unwrap();
+            src_buf_truncated.try_into().unwrap()
         } else {
             let pad = vec![0u8; 32 - src_buf.len()];
             let src_buf_padded = [pad, src_buf].concat();
-            dst_buf = src_buf_padded.try_into().unwrap();
-        }
+            src_buf_padded.try_into().unwrap()
+        };
         Scalar::from_bytes_unchecked(&dst_buf)
     }
 }
@@ -48,7 +47,7 @@ use num_traits::Euclid;
 mod tests {
     use super::*;
     use k256::{elliptic_curve::Field, Scalar};
-    use modulo_arithmetic::{modinv, prelude::const_secp256k1_order};
+    use modulo_arithmetic::{modinv, prelude::const_secp256k1_order, Secp256k1ModInt};
     use num_bigint::BigInt;
     use num_traits::Euclid;
     use rand::rngs::OsRng;
@@ -80,4 +79,20 @@ mod tests {
         let est_x_sc = x_bn.to_scalar();
         assert_eq!(x_sc, est_x_sc);
     }
+
+    #[test]
+    fn test_interop_modint() {
+        let mut x_sc = Scalar::random(&mut OsRng);
+        if Scalar::ZERO == x_sc {
+            x_sc += Scalar::ONE;
+        }
+        let mut x_mi = Secp256k1ModInt::new(x_sc.to_bigint());
+        assert_eq!(x_sc, x_mi.value().to_scalar());
+
+        x_sc = (x_sc * Scalar::from(114u32) + Scalar::from(514u32))
+            .invert()
+            .unwrap();
+        x_mi = (x_mi * Secp256k1ModInt::from(114) + Secp256k1ModInt::from(514)).inv();
+        assert_eq!(x_sc, x_mi.value().to_scalar());
+    }
 }
@@ -1,3 +1,4 @@
 pub mod interop;
 pub mod sesman;
-pub mod hash;
\ No newline at end of file
+pub mod hash;
+pub mod address;
\ No newline at end of file
@@ -2,13 +2,13 @@
 
 /// `Box<dyn Any + Send + Sync>` is used to store any type of object, \
 /// thus eliminate the need of de/serialization.
-static mut DB: OnceLock<DashMap<String, Box<dyn Any + Send + Sync>>> = OnceLock::new();
+static DB: OnceLock<DashMap<String, Box<dyn Any + Send + Sync>>> = OnceLock::new();
 
 pub async fn send<T>(topic: &str, src: usize, dst: usize, obj: &T)
 where
     T: Any + Send + Sync + Clone,
 {
-    let db = unsafe { DB.get_or_init(Default::default) };
+    let db = DB.get_or_init(Default::default);
     let key = format!("{}/{}->{}", topic, src, dst);
     db.insert(key, Box::new(obj.clone()));
 }
@@ -19,19 +19,24 @@ where
 {
     use tokio::time::{sleep, Duration};
 
-    let db = unsafe { DB.get_or_init(Default::default) };
+    let db = DB.get_or_init(Default::default);
     let key = format!("{}/{}->{}", topic, src, dst);
     while !db.contains_key(&key) {
         sleep(Duration::from_millis(200)).await;
     }
     let obj = db.get(&key).unwrap();
-    let obj = obj.downcast_ref::<T>().expect({
+    let obj = obj.downcast_ref::<T>().unwrap_or_else(|| {
         use std::any::type_name;
-        format!("Failed to interpret map entry as type {}", type_name::<T>(),).as_str()
+        panic!("Failed to interpret map entry as type {}", type_name::<T>())
     });
     obj.clone()
 }
 
+/* ========== Imports ========== */
+use std::{any::Any, sync::OnceLock};
+
+use dashmap::DashMap;
+
 #[cfg(test)]
 mod tests {
     #[derive(Clone, Debug, PartialEq, Eq)]
@@ -57,8 +62,3 @@ mod tests {
 
     use super::*;
 }
-
-/* ========== Imports ========== */
-use std::{any::Any, sync::OnceLock};
-
-use dashmap::DashMap;
@@ -1,5 +1,6 @@
-//! Integration test for DKG (Distributed Key Generation)
-//!   and recovery (retrieval) of the main secret.
+//! Integration test for DKG (Distributed Key Generation), threshold signing,
+//!   recovery (retrieval) of the main secret, threshold ECDH, threshold ElGamal
+//!   share repair, and hierarchical DKG.
 mod thread_fn;
 use thread_fn::*;
 
@@ -30,6 +31,16 @@ async fn integration() {
         .cloned()
         .collect();
 
+    let mut handles = vec![];
+    for id in attendants.iter() {
+        let att = attendants.clone();
+        let handle = tokio::spawn(thread_sign(*id, att, b"hello world".to_vec()));
+        handles.push(handle);
+    }
+    for h in handles {
+        h.await.unwrap();
+    }
+
     /* ===== test recovery ===== */
     let mut handles = vec![];
     for id in attendants.iter() {
@@ -40,4 +51,75 @@ async fn integration() {
     for h in handles {
         h.await.unwrap();
     }
-}
\ No newline at end of file
+
+    /* ===== test threshold ECDH ===== */
+    use k256::{elliptic_curve::Field, Scalar};
+    let peer_sk = Scalar::random(&mut rng);
+    let mut handles = vec![];
+    for id in attendants.iter() {
+        let att = attendants.clone();
+        let handle = tokio::spawn(thread_ecdh(*id, att, peer_sk));
+        handles.push(handle);
+    }
+    for h in handles {
+        h.await.unwrap();
+    }
+
+    /* ===== test threshold ElGamal ===== */
+    let mut handles = vec![];
+    for id in attendants.iter() {
+        let att = attendants.clone();
+        let handle = tokio::spawn(thread_elgamal(*id, att));
+        handles.push(handle);
+    }
+    for h in handles {
+        h.await.unwrap();
+    }
+
+    /* ===== test share repair ===== */
+    let lost_id = members[0];
+    let helpers: Vec<usize> = members[1..=SAMPLE_T].to_vec();
+    let mut handles = vec![];
+    for id in helpers.iter() {
+        let hlp = helpers.clone();
+        let handle = tokio::spawn(thread_repair_helper(*id, hlp, lost_id));
+        handles.push(handle);
+    }
+    handles.push(tokio::spawn(thread_repair_lost(lost_id, helpers.clone())));
+    for h in handles {
+        h.await.unwrap();
+    }
+
+    /* ===== test hierarchical DKG ===== */
+    // Members 1..=3 are the board. Any 4 members including 2 of the board can recover.
+    let thresholds: Vec<usize> = vec![2, 4];
+    let levels: HashMap<usize, usize> = (1..=7)
+        .map(|id| (id, if id <= 3 { 0 } else { 1 }))
+        .collect();
+    let mut handles = vec![];
+    for id in levels.keys() {
+        let handle = tokio::spawn(thread_dkg_hierarchical(
+            *id,
+            thresholds.clone(),
+            levels.clone(),
+        ));
+        handles.push(handle);
+    }
+    for h in handles {
+        h.await.unwrap();
+    }
+
+    for (attendants, authorized) in [(vec![1, 3, 5, 6], true), (vec![2, 4, 5, 6, 7], false)] {
+        let mut handles = vec![];
+        for id in attendants.iter() {
+            let att = attendants.clone();
+            let handle = tokio::spawn(thread_recover_hierarchical(*id, att, authorized));
+            handles.push(handle);
+        }
+        for h in handles {
+            h.await.unwrap();
+        }
+    }
+}
+
+use std::collections::HashMap;
@@ -2,11 +2,20 @@ use std::sync::OnceLock;
 
 use dashmap::DashMap;
 
-pub static mut DISK: OnceLock<DashMap<usize, Vec<u8>>> = OnceLock::new();
+pub static DISK: OnceLock<DashMap<usize, Vec<u8>>> = OnceLock::new();
+pub static HIER_DISK: OnceLock<DashMap<usize, Vec<u8>>> = OnceLock::new();
 
 mod thread_dkg;
 pub use thread_dkg::*;
 mod thread_recover;
 pub use thread_recover::*;
 mod thread_sign;
-pub use thread_sign::*;
\ No newline at end of file
+pub use thread_sign::*;
+mod thread_ecdh;
+pub use thread_ecdh::*;
+mod thread_elgamal;
+pub use thread_elgamal::*;
+mod thread_repair;
+pub use thread_repair::*;
+mod thread_dkg_hierarchical;
+pub use thread_dkg_hierarchical::*;
@@ -37,9 +37,9 @@ pub async fn thread_dkg(my_id: usize, t: usize, members: Vec<usize>) {
     }
 
     // Receive VSS shares from other participants.
-    let mut vss_secret = const_0();
+    let mut vss_secret = Scalar::ZERO;
     for i in members.iter() {
-        let polyval_ji: BigInt = recv("vss_share", *i, my_id).await; // j is my_id
+        let polyval_ji: Scalar = recv("vss_share", *i, my_id).await; // j is my_id
         let (poly_com, polyval_com) =
             vss_coms[i].prepare_to_check_vss_com(my_id, &polyval_ji);
         assert_eq!(
@@ -59,7 +59,7 @@ pub async fn thread_dkg(my_id: usize, t: usize, members: Vec<usize>) {
     };
 
     // Save keystore to "disk".
-    let disk = unsafe { super::DISK.get_or_init(DashMap::new) };
+    let disk = super::DISK.get_or_init(DashMap::new);
     let buf = serde_pickle::to_vec(&keystore, serde_pickle::SerOptions::default()).unwrap();
     disk.insert(my_id, buf);
 
@@ -73,5 +73,4 @@ use std::collections::HashMap;
 
 use dashmap::DashMap;
 use feldman_vss::{sesman::*, KeyStore, VssCommitment, VssLocalScheme};
-use modulo_arithmetic::prelude::*;
-use num_bigint::BigInt;
+use k256::Scalar;
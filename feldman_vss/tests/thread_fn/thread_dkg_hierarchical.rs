@@ -0,0 +1,89 @@
+pub async fn thread_dkg_hierarchical(
+    my_id: usize,
+    thresholds: Vec<usize>,
+    levels: HashMap<usize, usize>, // level of each member
+) {
+    let t = *thresholds.last().unwrap();
+    let my_level = levels[&my_id];
+
+    // Same as `thread_dkg`, except that each member receives a derivative for its level.
+    let my_scheme = VssLocalScheme::new(t);
+    let my_com = my_scheme.commit();
+    send("hvss_com", my_id, 0, &my_com).await;
+
+    let mut vss_coms: HashMap<usize, VssCommitment> = HashMap::new();
+    for i in levels.keys() {
+        let com: VssCommitment = recv("hvss_com", *i, 0).await;
+        assert_eq!(com.len(), t);
+        vss_coms.insert(*i, com);
+    }
+
+    for (i, level) in levels.iter() {
+        let order = derivative_order(*level, &thresholds);
+        let polyval_ji = my_scheme.share_to_derivative(*i, order); // j is my_id
+        send("hvss_share", my_id, *i, &polyval_ji).await;
+    }
+
+    let my_order = derivative_order(my_level, &thresholds);
+    let mut vss_secret = Scalar::ZERO;
+    for i in levels.keys() {
+        let polyval_ji: Scalar = recv("hvss_share", *i, my_id).await; // j is my_id
+        let (poly_com, polyval_com) =
+            vss_coms[i].prepare_to_check_vss_derivative(my_id, my_order, &polyval_ji);
+        assert_eq!(
+            poly_com, polyval_com,
+            "VSS share verification failed for {} -> {}",
+            i, my_id
+        );
+        vss_secret += &polyval_ji;
+    }
+
+    let keystore = HierarchicalKeyStore {
+        id: my_id,
+        level: my_level,
+        thresholds,
+        vss_scheme: my_scheme,
+        vss_coms,
+        vss_secret,
+    };
+    assert_eq!(
+        ProjectivePoint::GENERATOR * keystore.vss_secret,
+        keystore.pk_share(my_id, my_level)
+    );
+
+    let disk = super::HIER_DISK.get_or_init(DashMap::new);
+    let buf = serde_pickle::to_vec(&keystore, serde_pickle::SerOptions::default()).unwrap();
+    disk.insert(my_id, buf);
+}
+
+pub async fn thread_recover_hierarchical(my_id: usize, attendants: Vec<usize>, authorized: bool) {
+    let disk = super::HIER_DISK.get_or_init(DashMap::new);
+    let buf = disk.get(&my_id).unwrap();
+    let keystore: HierarchicalKeyStore =
+        serde_pickle::from_slice(&buf, serde_pickle::DeOptions::default()).unwrap();
+
+    send("hvss_secret", my_id, 0, &keystore.share()).await;
+    let mut shares: Vec<HierarchicalShare<Scalar>> = Vec::new();
+    for i in attendants.iter() {
+        let share: HierarchicalShare<Scalar> = recv("hvss_secret", *i, 0).await;
+        shares.push(share);
+    }
+
+    let view: Vec<&HierarchicalShare<Scalar>> = shares.iter().collect();
+    match recover_hierarchical(&view, &keystore.thresholds) {
+        Some(sk) => {
+            assert!(authorized);
+            assert_eq!(ProjectivePoint::GENERATOR * sk, keystore.pk());
+        }
+        None => assert!(!authorized),
+    }
+}
+
+use std::collections::HashMap;
+
+use dashmap::DashMap;
+use feldman_vss::{
+    recover_hierarchical, sesman::*, HierarchicalKeyStore, VssCommitment, VssLocalScheme,
+};
+use k256::{ProjectivePoint, Scalar};
+use shamir_secret_sharing::{derivative_order, HierarchicalShare};
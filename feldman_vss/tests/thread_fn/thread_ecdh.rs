@@ -0,0 +1,33 @@
+pub async fn thread_ecdh(my_id: usize, attendants: Vec<usize>, peer_sk: Scalar) {
+    // Load keystore from "disk".
+    assert!(attendants.contains(&my_id));
+    let disk = super::DISK.get_or_init(DashMap::new);
+    let buf = disk.get(&my_id).unwrap();
+    let keystore: KeyStore =
+        serde_pickle::from_slice(&buf, serde_pickle::DeOptions::default()).unwrap();
+    assert_eq!(keystore.id, my_id);
+
+    // The outside party only reveals its public key.
+    let peer_pk = ProjectivePoint::GENERATOR * peer_sk;
+
+    // Send ECDH share.
+    let my_share = EcdhShare::new(&keystore, &peer_pk);
+    send("ecdh_share", my_id, 0, &my_share).await;
+
+    // Receive ECDH shares.
+    let mut shares: Vec<EcdhShare> = Vec::new();
+    for i in attendants.iter() {
+        let share: EcdhShare = recv("ecdh_share", *i, 0).await;
+        shares.push(share);
+    }
+
+    // Interpolate `P * x` in the exponent.
+    let shared = combine_ecdh_shares(&keystore, &peer_pk, &shares);
+
+    // The outside party computes the same point from its own side.
+    assert_eq!(shared, keystore.pk() * peer_sk);
+}
+
+use dashmap::DashMap;
+use feldman_vss::{combine_ecdh_shares, sesman::*, EcdhShare, KeyStore};
+use k256::{ProjectivePoint, Scalar};
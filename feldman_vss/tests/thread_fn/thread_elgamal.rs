@@ -0,0 +1,50 @@
+/// A toy vote: every attendant encrypts its ballot to the main public key,
+/// the ballots are tallied homomorphically, and the tally is decrypted by threshold.
+pub async fn thread_elgamal(my_id: usize, attendants: Vec<usize>) {
+    // Load keystore from "disk".
+    assert!(attendants.contains(&my_id));
+    let disk = super::DISK.get_or_init(DashMap::new);
+    let buf = disk.get(&my_id).unwrap();
+    let keystore: KeyStore =
+        serde_pickle::from_slice(&buf, serde_pickle::DeOptions::default()).unwrap();
+    assert_eq!(keystore.id, my_id);
+
+    // The lower half of the attendants by id vote "yes", the rest "no",
+    // so the tally adds up both 0s and 1s.
+    let mut yes_voters = attendants.clone();
+    yes_voters.sort();
+    yes_voters.truncate(attendants.len() / 2);
+    let ballot = yes_voters.contains(&my_id) as u64;
+    let my_ct = ElGamalCiphertext::encrypt_u64(&keystore.pk(), ballot);
+    send("elgamal_ballot", my_id, 0, &my_ct).await;
+
+    // Tally the ballots without decrypting any of them.
+    let mut tally: Option<ElGamalCiphertext> = None;
+    for i in attendants.iter() {
+        let ct: ElGamalCiphertext = recv("elgamal_ballot", *i, 0).await;
+        tally = Some(match tally {
+            None => ct,
+            Some(acc) => &acc + &ct,
+        });
+    }
+    let tally = tally.unwrap();
+
+    // Send partial decryption of the tally.
+    let my_part = tally.partial_decrypt(&keystore);
+    send("elgamal_part", my_id, 0, &my_part).await;
+
+    // Receive partial decryptions.
+    let mut parts: Vec<PartialDecryption> = Vec::new();
+    for i in attendants.iter() {
+        let part: PartialDecryption = recv("elgamal_part", *i, 0).await;
+        parts.push(part);
+    }
+
+    // Decrypt the tally.
+    let n_yes = yes_voters.len() as u64;
+    let est = tally.decrypt_u64(&keystore, &parts, attendants.len() as u64);
+    assert_eq!(est, Some(n_yes));
+}
+
+use dashmap::DashMap;
+use feldman_vss::{sesman::*, ElGamalCiphertext, KeyStore, PartialDecryption};
@@ -0,0 +1,52 @@
+pub async fn thread_repair_helper(my_id: usize, helpers: Vec<usize>, lost_id: usize) {
+    // Load keystore from "disk".
+    assert!(helpers.contains(&my_id));
+    let disk = super::DISK.get_or_init(DashMap::new);
+    let buf = disk.get(&my_id).unwrap();
+    let keystore: KeyStore =
+        serde_pickle::from_slice(&buf, serde_pickle::DeOptions::default()).unwrap();
+    assert_eq!(keystore.id, my_id);
+
+    // Send pieces of the masked share to other helpers.
+    let pieces = repair_pieces_for(&keystore, &helpers, lost_id);
+    for (l, piece) in pieces.iter() {
+        send("repair_piece", my_id, *l, piece).await;
+    }
+
+    // Receive pieces from other helpers, and send their sum to the lost member.
+    let mut received: Vec<Scalar> = Vec::new();
+    for i in helpers.iter() {
+        let piece: Scalar = recv("repair_piece", *i, my_id).await;
+        received.push(piece);
+    }
+    let sigma = repair_sum_pieces(&received);
+    send("repair_sigma", my_id, lost_id, &sigma).await;
+    send("repair_coms", my_id, lost_id, &keystore.vss_coms).await;
+}
+
+pub async fn thread_repair_lost(my_id: usize, helpers: Vec<usize>) {
+    // Receive sums from the helpers.
+    let mut sigmas: Vec<Scalar> = Vec::new();
+    for i in helpers.iter() {
+        let sigma: Scalar = recv("repair_sigma", *i, my_id).await;
+        sigmas.push(sigma);
+    }
+    let vss_coms: HashMap<usize, VssCommitment> = recv("repair_coms", helpers[0], my_id).await;
+
+    let keystore = KeyStore::repaired(my_id, vss_coms, &sigmas);
+
+    // Compare with the keystore before it was "lost".
+    let disk = super::DISK.get_or_init(DashMap::new);
+    let buf = disk.get(&my_id).unwrap();
+    let original: KeyStore =
+        serde_pickle::from_slice(&buf, serde_pickle::DeOptions::default()).unwrap();
+    assert_eq!(keystore.vss_secret, original.vss_secret);
+    assert_eq!(keystore.pk(), original.pk());
+    assert_eq!(keystore.t(), original.t());
+}
+
+use std::collections::HashMap;
+
+use dashmap::DashMap;
+use feldman_vss::{repair_pieces_for, repair_sum_pieces, sesman::*, KeyStore, VssCommitment};
+use k256::Scalar;
@@ -1,3 +1,43 @@
-pub async fn thread_sign(my_id: usize, attendants: Vec<usize>) {
+pub async fn thread_sign(my_id: usize, attendants: Vec<usize>, msg: Vec<u8>) {
+    // Load keystore from "disk".
+    assert!(attendants.contains(&my_id));
+    let disk = super::DISK.get_or_init(DashMap::new);
+    let buf = disk.get(&my_id).unwrap();
+    let keystore: KeyStore =
+        serde_pickle::from_slice(&buf, serde_pickle::DeOptions::default()).unwrap();
+    assert_eq!(keystore.id, my_id);
 
-}
\ No newline at end of file
+    // Round 1: send nonce commitment.
+    let (my_nonces, my_com) = SigningNonces::new(my_id);
+    send("sign_com", my_id, 0, &my_com).await;
+
+    // Receive nonce commitments.
+    let mut coms: Vec<SigningCommitment> = Vec::new();
+    for i in attendants.iter() {
+        let com: SigningCommitment = recv("sign_com", *i, 0).await;
+        coms.push(com);
+    }
+
+    // Round 2: send partial signature.
+    let my_psig = sign_partial(&keystore, my_nonces, &coms, &msg);
+    send("sign_psig", my_id, 0, &my_psig).await;
+
+    // Receive partial signatures.
+    let mut psigs: Vec<PartialSignature> = Vec::new();
+    for i in attendants.iter() {
+        let psig: PartialSignature = recv("sign_psig", *i, 0).await;
+        psigs.push(psig);
+    }
+
+    // Aggregate. Each partial signature is checked against its sender's verification share,
+    // so a failure names the misbehaving ids.
+    let sig = aggregate_signature(&keystore, &coms, &msg, &psigs)
+        .unwrap_or_else(|e| panic!("Signing failed at {}: {}", my_id, e));
+    assert!(sig.verify(&keystore.pk(), &msg));
+}
+
+use dashmap::DashMap;
+use feldman_vss::{
+    aggregate_signature, sesman::*, sign_partial, KeyStore, PartialSignature, SigningCommitment,
+    SigningNonces,
+};
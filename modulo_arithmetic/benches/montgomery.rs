@@ -0,0 +1,38 @@
+//! `modpow` against `MontgomeryCtx::modpow` and the constant-time ladder, at RSA / Paillier sizes.
+//!
+//! Run with `cargo bench -p modulo_arithmetic`.
+
+fn benches(c: &mut Criterion) {
+    let mut rng = rand::thread_rng();
+    let mut group = c.benchmark_group("modpow");
+    group.sample_size(10);
+    for bits in [1024u64, 2048, 4096] {
+        // Any odd modulus of full size will do for timing.
+        let p = rng.gen_biguint(bits) | (BigUint::from(1u8) << (bits - 1)) | BigUint::from(1u8);
+        let p = BigInt::from(p);
+        let base = rng.gen_bigint_range(&BigInt::from(2), &p);
+        let exp = rng.gen_bigint_range(&BigInt::from(2), &p);
+
+        group.bench_with_input(BenchmarkId::new("modpow", bits), &bits, |b, _| {
+            b.iter(|| modpow(&base, &exp, &p))
+        });
+        let ctx = MontgomeryCtx::new(&p);
+        group.bench_with_input(BenchmarkId::new("montgomery", bits), &bits, |b, _| {
+            b.iter(|| ctx.modpow(&base, &exp))
+        });
+        group.bench_with_input(BenchmarkId::new("montgomery_ct", bits), &bits, |b, _| {
+            b.iter(|| modpow_ct(&base, &exp, &p))
+        });
+        group.bench_with_input(BenchmarkId::new("num_bigint", bits), &bits, |b, _| {
+            b.iter(|| base.modpow(&exp, &p))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(montgomery, benches);
+criterion_main!(montgomery);
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use modulo_arithmetic::{modpow, modpow_ct, MontgomeryCtx};
+use num_bigint::{BigInt, BigUint, RandBigInt};
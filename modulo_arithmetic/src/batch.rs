@@ -0,0 +1,50 @@
+/// Inverses of all `xs` modulo `p` with a single `modinv`, by Montgomery's trick.
+///
+/// With prefix products `c_i = x_0 \cdots x_i`, invert `c_{n-1}` once, then walk back:
+/// `x_i^{-1} = c_{i-1} \cdot c_i^{-1}` and `c_{i-1}^{-1} = x_i \cdot c_i^{-1}`.
+/// Costs `3(n-1)` multiplications besides the inversion.
+/// Panics like `modinv` if any `x_i` is not invertible.
+pub fn batch_modinv(xs: &[BigInt], p: &BigInt) -> Vec<BigInt> {
+    if xs.is_empty() {
+        return vec![];
+    }
+    let mut prefix: Vec<BigInt> = Vec::with_capacity(xs.len());
+    let mut acc = const_1();
+    for x in xs.iter() {
+        acc = (acc * x).rem_euclid(p);
+        prefix.push(acc.clone());
+    }
+    let mut acc_inv = modinv(&acc, p); // (x_0 \cdots x_i)^{-1}, for i from n-1 down
+    let mut invs = vec![const_0(); xs.len()];
+    for i in (1..xs.len()).rev() {
+        invs[i] = (&prefix[i - 1] * &acc_inv).rem_euclid(p);
+        acc_inv = (&acc_inv * &xs[i]).rem_euclid(p);
+    }
+    invs[0] = acc_inv;
+    invs
+}
+
+use crate::modinv;
+use crate::prelude::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_batch_modinv() {
+        let p = const_mersenne12();
+        let xs: Vec<BigInt> = (1..50i64)
+            .map(|i| BigInt::from(i * i * 1919 - 810))
+            .collect();
+        let invs = batch_modinv(&xs, &p);
+        for (x, inv) in xs.iter().zip(invs.iter()) {
+            assert_eq!(inv, &modinv(x, &p));
+        }
+        assert_eq!(
+            batch_modinv(&[BigInt::from(3)], &BigInt::from(7)),
+            vec![BigInt::from(5)]
+        );
+        assert!(batch_modinv(&[], &p).is_empty());
+    }
+}
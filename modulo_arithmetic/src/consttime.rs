@@ -0,0 +1,69 @@
+/// `base^exp mod p` for secret `exp`, by `MontgomeryCtx::pow_ct`.
+///
+/// Requires an odd `p > 1` and `0 <= exp < 2^bits(p)`, which covers exponents
+/// reduced modulo `p` or any group order below it. The ladder runs `bits(p)` steps
+/// whatever `exp` is. Prefer a reused `MontgomeryCtx` when the modulus is fixed.
+pub fn modpow_ct(base: &BigInt, exp: &BigInt, p: &BigInt) -> BigInt {
+    let ctx = MontgomeryCtx::new(p);
+    let y = ctx.pow_ct(&ctx.to_montgomery(base), exp, p.bits());
+    ctx.from_montgomery(&y)
+}
+
+/// `a^{-1} mod p` for secret `a` and an odd prime `p`, by Fermat's little theorem.
+/// Unlike `modinv`, the running time does not depend on `a`. Maps 0 to 0.
+pub fn modinv_ct(a: &BigInt, p: &BigInt) -> BigInt {
+    let ctx = MontgomeryCtx::new(p);
+    let y = ctx.inv_ct(&ctx.to_montgomery(a));
+    ctx.from_montgomery(&y)
+}
+
+use crate::prelude::*;
+use crate::MontgomeryCtx;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{modinv, modpow};
+
+    #[test]
+    fn test_modpow_ct() {
+        for p in [
+            BigInt::from(1919),
+            const_mersenne12(),
+            const_secp256k1_order(),
+            (const_1() << 2048) - 159,
+        ] {
+            let base = &p / 7 + 12345;
+            for exp in [
+                const_0(),
+                const_1(),
+                &p - 1,
+                (const_1() << (p.bits() - 1)) + 0b1011_0111u8,
+                &p / 3,
+            ] {
+                assert_eq!(modpow_ct(&base, &exp, &p), modpow(&base, &exp, &p));
+            }
+        }
+    }
+
+    #[test]
+    fn test_modinv_ct() {
+        for p in [
+            BigInt::from(7919),
+            const_mersenne12(),
+            const_secp256k1_order(),
+        ] {
+            for a in [const_1(), BigInt::from(2), &p - 1, &p / 3, &p / 5 - &p] {
+                assert_eq!(modinv_ct(&a, &p), modinv(&a, &p));
+            }
+            assert_eq!(modinv_ct(&const_0(), &p), const_0());
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "exponent out of range")]
+    fn test_modpow_ct_range() {
+        let p = BigInt::from(1919);
+        modpow_ct(&BigInt::from(3), &BigInt::from(2048), &p);
+    }
+}
@@ -0,0 +1,197 @@
+/// Solution of a system of congruences: `x == residues[i] (mod moduli[i])` for all `i`
+/// iff `x == self.x (mod self.lcm)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CrtSolution {
+    pub x: BigInt,   // 0 <= x < lcm
+    pub lcm: BigInt, // lcm of the moduli
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CrtError {
+    /// `residues` and `moduli` differ in length, or are empty.
+    LengthMismatch,
+    /// `moduli[index] <= 0`.
+    NonPositiveModulus { index: usize },
+    /// Congruence `index` contradicts the ones before it,
+    /// i.e. two residues differ modulo the gcd of their moduli.
+    Inconsistent { index: usize },
+}
+
+/// Solve `x == residues[i] (mod moduli[i])`.
+///
+/// The moduli need not be pairwise coprime. Two congruences merge as
+/// $$
+/// x = a_1 + m_1 \cdot \left(\frac{a_2 - a_1}{g} \cdot \left(\frac{m_1}{g}\right)^{-1} \bmod \frac{m_2}{g}\right),
+/// \quad g = \gcd(m_1, m_2)
+/// $$
+/// which exists iff `g` divides `a_2 - a_1`.
+pub fn crt(residues: &[BigInt], moduli: &[BigInt]) -> Result<CrtSolution, CrtError> {
+    if residues.len() != moduli.len() || moduli.is_empty() {
+        return Err(CrtError::LengthMismatch);
+    }
+    let mut sol = CrtSolution {
+        x: const_0(),
+        lcm: const_1(),
+    };
+    for (index, (a, m)) in residues.iter().zip(moduli.iter()).enumerate() {
+        if !m.is_positive() {
+            return Err(CrtError::NonPositiveModulus { index });
+        }
+        let obj = algo_extended_euclidean(&sol.lcm, m);
+        let g = obj.gcd.abs();
+        let diff = a - &sol.x;
+        if !diff.rem_euclid(&g).is_zero() {
+            return Err(CrtError::Inconsistent { index });
+        }
+        // bezout_x * lcm + bezout_y * m == g, so bezout_x == (lcm/g)^{-1} (mod m/g).
+        let m_g = m / &g;
+        let t = ((diff / &g) * obj.bezout_x).rem_euclid(&m_g);
+        let lcm = &sol.lcm * &m_g;
+        sol.x = (&sol.x + &sol.lcm * t).rem_euclid(&lcm);
+        sol.lcm = lcm;
+    }
+    Ok(sol)
+}
+
+/// Exponentiation modulo `n = p * q` for known distinct odd primes `p` and `q`.
+///
+/// Exponents are reduced modulo `p - 1` and `q - 1`, the halves are computed with
+/// half-size Montgomery arithmetic, and Garner's formula recombines them:
+/// $$
+/// y = y_q + q \cdot ((y_p - y_q) \cdot q^{-1} \bmod p)
+/// $$
+#[derive(Clone, Debug)]
+pub struct CrtModPow {
+    n: BigInt,
+    p: MontgomeryCtx,
+    q: MontgomeryCtx,
+    q_inv_p: BigInt, // q^{-1} mod p
+}
+
+impl CrtModPow {
+    pub fn new(p: &BigInt, q: &BigInt) -> Self {
+        assert!(p != q, "p and q must be distinct");
+        Self {
+            n: p * q,
+            p: MontgomeryCtx::new(p),
+            q: MontgomeryCtx::new(q),
+            q_inv_p: modinv(q, p),
+        }
+    }
+
+    pub fn modulus(&self) -> &BigInt {
+        &self.n
+    }
+
+    /// `base^exp mod n`, for any `base`. A negative `exp` needs `base` invertible mod `n`.
+    pub fn modpow(&self, base: &BigInt, exp: &BigInt) -> BigInt {
+        let mut base = base.rem_euclid(&self.n);
+        let mut exp = exp.clone();
+        if exp.is_negative() {
+            base = modinv(&base, &self.n);
+            exp = -exp;
+        }
+        let y_p = Self::half(&self.p, &base, &exp);
+        let y_q = Self::half(&self.q, &base, &exp);
+        let p = self.p.modulus();
+        let h = ((y_p - &y_q) * &self.q_inv_p).rem_euclid(p);
+        y_q + self.q.modulus() * h
+    }
+
+    /// `base^exp mod r` for the prime `r` of `ctx`.
+    fn half(ctx: &MontgomeryCtx, base: &BigInt, exp: &BigInt) -> BigInt {
+        let r_minus_1 = ctx.modulus() - const_1();
+        let mut e = exp.rem_euclid(&r_minus_1);
+        // Fermat: b^(r-1) is 1 like b^0 for b coprime to r, but stays 0 for b == 0 (mod r).
+        if e.is_zero() && exp.is_positive() {
+            e = r_minus_1;
+        }
+        ctx.modpow(base, &e)
+    }
+}
+
+impl std::fmt::Display for CrtError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CrtError::LengthMismatch => {
+                write!(f, "need equally many residues and moduli, at least one")
+            }
+            CrtError::NonPositiveModulus { index } => {
+                write!(f, "modulus {} is not positive", index)
+            }
+            CrtError::Inconsistent { index } => {
+                write!(f, "congruence {} contradicts the previous ones", index)
+            }
+        }
+    }
+}
+
+impl std::error::Error for CrtError {}
+
+use crate::prelude::*;
+use crate::{algo_extended_euclidean, modinv, MontgomeryCtx};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modpow;
+
+    fn ints(v: &[i64]) -> Vec<BigInt> {
+        v.iter().map(|x| BigInt::from(*x)).collect()
+    }
+
+    #[test]
+    fn test_crt() {
+        // Sunzi Suanjing: x == 2 (mod 3), x == 3 (mod 5), x == 2 (mod 7).
+        let sol = crt(&ints(&[2, 3, 2]), &ints(&[3, 5, 7])).unwrap();
+        assert_eq!(
+            sol,
+            CrtSolution {
+                x: BigInt::from(23),
+                lcm: BigInt::from(105)
+            }
+        );
+
+        // Non-coprime but consistent.
+        let sol = crt(&ints(&[3, 9, -1]), &ints(&[4, 6, 10])).unwrap();
+        assert_eq!(
+            sol,
+            CrtSolution {
+                x: BigInt::from(39),
+                lcm: BigInt::from(60)
+            }
+        );
+
+        // Non-coprime and inconsistent: x is odd and even at once.
+        assert_eq!(
+            crt(&ints(&[1, 3, 2]), &ints(&[5, 4, 6])),
+            Err(CrtError::Inconsistent { index: 2 })
+        );
+        assert_eq!(
+            crt(&ints(&[1]), &ints(&[0])),
+            Err(CrtError::NonPositiveModulus { index: 0 })
+        );
+        assert_eq!(
+            crt(&ints(&[1, 2]), &ints(&[3])),
+            Err(CrtError::LengthMismatch)
+        );
+    }
+
+    #[test]
+    fn test_crt_modpow() {
+        let p = const_mersenne12();
+        let q = BigInt::from(1000000007);
+        let ctx = CrtModPow::new(&p, &q);
+        let n = &p * &q;
+        for (base, exp) in [
+            (BigInt::from(114514), BigInt::from(1919810)),
+            (&n - 2, &n + 5),
+            (BigInt::from(7), BigInt::from(-3)),
+            (p.clone(), BigInt::from(3)), // not coprime to n
+            (q.clone(), &p - 1),          // exp is a multiple of p-1
+            (BigInt::from(5), const_0()),
+        ] {
+            assert_eq!(ctx.modpow(&base, &exp), modpow(&base, &exp, &n));
+        }
+    }
+}
@@ -0,0 +1,322 @@
+//! Discrete logarithms: find `x` with `g^x == h` in a cyclic group.
+//!
+//! * `bsgs`: baby-step giant-step for `x < bound`, `O(sqrt(bound))` time and memory.
+//! * `pollard_rho`: same time for a group of known order, but constant memory.
+//! * `pohlig_hellman`: reduces a group of smooth order `\prod q^e` to subgroups of prime
+//!   order `q`, so the cost is governed by the largest `q` rather than the whole order.
+//!   This is why the order of a cryptographic group must have a large prime factor.
+//!
+//! The groups are described by `CyclicGroup`, e.g. `MulModGroup` here,
+//! or elliptic curve points in `feldman_vss`.
+
+/// A group written multiplicatively, with elements of type `Elem`.
+pub trait CyclicGroup {
+    type Elem: Clone + PartialEq;
+
+    fn identity(&self) -> Self::Elem;
+    fn op(&self, a: &Self::Elem, b: &Self::Elem) -> Self::Elem;
+    fn inv(&self, a: &Self::Elem) -> Self::Elem;
+
+    /// Canonical encoding: equal iff the elements are equal. Used for hashing.
+    fn to_bytes(&self, a: &Self::Elem) -> Vec<u8>;
+
+    /// `a^k`, by square-and-multiply.
+    fn pow(&self, a: &Self::Elem, k: &BigInt) -> Self::Elem {
+        let base = if k.is_negative() {
+            self.inv(a)
+        } else {
+            a.clone()
+        };
+        let k = k.abs();
+        let mut y = self.identity();
+        for i in (0..k.bits()).rev() {
+            y = self.op(&y, &y);
+            if k.bit(i) {
+                y = self.op(&y, &base);
+            }
+        }
+        y
+    }
+}
+
+/// The multiplicative group of integers modulo `p`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MulModGroup {
+    p: BigInt,
+}
+
+impl MulModGroup {
+    pub fn new(p: &BigInt) -> Self {
+        assert!(p > &const_1(), "modulus must be > 1");
+        Self { p: p.clone() }
+    }
+
+    pub fn modulus(&self) -> &BigInt {
+        &self.p
+    }
+}
+
+impl CyclicGroup for MulModGroup {
+    type Elem = BigInt;
+
+    fn identity(&self) -> BigInt {
+        const_1()
+    }
+
+    fn op(&self, a: &BigInt, b: &BigInt) -> BigInt {
+        (a * b).rem_euclid(&self.p)
+    }
+
+    fn inv(&self, a: &BigInt) -> BigInt {
+        modinv(a, &self.p)
+    }
+
+    fn to_bytes(&self, a: &BigInt) -> Vec<u8> {
+        a.rem_euclid(&self.p).to_bytes_le().1
+    }
+
+    fn pow(&self, a: &BigInt, k: &BigInt) -> BigInt {
+        modpow(a, k, &self.p)
+    }
+}
+
+/// Baby-step giant-step: the least `x` in `[0, bound)` with `g^x == h`, if any.
+///
+/// With `m = ceil(sqrt(bound))` and `x = i * m + j`, tabulate the baby steps `g^j`
+/// for `j < m`, then walk the giant steps `h * g^{-im}` until one hits the table.
+pub fn bsgs<G: CyclicGroup>(group: &G, g: &G::Elem, h: &G::Elem, bound: &BigInt) -> Option<BigInt> {
+    if !bound.is_positive() {
+        return None;
+    }
+    let mut m = bound.sqrt();
+    if &(&m * &m) < bound {
+        m += 1u8;
+    }
+    let m = m
+        .to_u64()
+        .expect("bound too large for baby-step giant-step");
+
+    let mut baby_steps: HashMap<Vec<u8>, u64> = HashMap::new();
+    let mut g_j = group.identity();
+    for j in 0..m {
+        baby_steps.entry(group.to_bytes(&g_j)).or_insert(j);
+        g_j = group.op(&g_j, g);
+    }
+    let giant = group.inv(&g_j); // g^{-m}
+    let mut gamma = h.clone();
+    for i in 0..m {
+        if let Some(j) = baby_steps.get(&group.to_bytes(&gamma)) {
+            let x = BigInt::from(i) * m + j;
+            return (&x < bound).then_some(x);
+        }
+        gamma = group.op(&gamma, &giant);
+    }
+    None
+}
+
+/// Pollard's rho: some `x` in `[0, order)` with `g^x == h`, where `order` is the order of `g`.
+///
+/// A pseudo-random walk on `g^a h^b`, choosing by a hash of the element
+/// among multiplying by `g`, squaring and multiplying by `h`, must cycle.
+/// Floyd's cycle finding spots a collision `g^a h^b == g^a' h^b'`,
+/// and `(b' - b) x == a - a' (mod order)` is solved for `x`.
+/// Returns `None` if `h` is not in the group generated by `g`, after a few restarts.
+pub fn pollard_rho<G: CyclicGroup>(
+    group: &G,
+    g: &G::Elem,
+    h: &G::Elem,
+    order: &BigInt,
+) -> Option<BigInt> {
+    pollard_rho_with_rng(group, g, h, order, &mut rand::thread_rng())
+}
+
+/// Same as `pollard_rho`, drawing the starting points of the walks from `rng`.
+pub fn pollard_rho_with_rng<G: CyclicGroup, R: CryptoRng + RngCore>(
+    group: &G,
+    g: &G::Elem,
+    h: &G::Elem,
+    order: &BigInt,
+    rng: &mut R,
+) -> Option<BigInt> {
+    const RESTARTS: usize = 16;
+    const MAX_CANDIDATES: u64 = 1 << 16;
+
+    let n = order;
+    // (element, a, b) with element == g^a h^b.
+    let step = |(x, a, b): &(G::Elem, BigInt, BigInt)| -> (G::Elem, BigInt, BigInt) {
+        let mut hasher = DefaultHasher::new();
+        group.to_bytes(x).hash(&mut hasher);
+        match hasher.finish() % 3 {
+            0 => (group.op(x, g), (a + 1u8) % n, b.clone()),
+            1 => (group.op(x, x), (a * 2u8) % n, (b * 2u8) % n),
+            _ => (group.op(x, h), a.clone(), (b + 1u8) % n),
+        }
+    };
+    for _ in 0..RESTARTS {
+        let a = rng.gen_bigint_range(&const_0(), n);
+        let b = rng.gen_bigint_range(&const_0(), n);
+        let start = (group.op(&group.pow(g, &a), &group.pow(h, &b)), a, b);
+        let mut tortoise = step(&start);
+        let mut hare = step(&tortoise);
+        while tortoise.0 != hare.0 {
+            tortoise = step(&tortoise);
+            hare = step(&step(&hare));
+        }
+        let (_, a1, b1) = tortoise;
+        let (_, a2, b2) = hare;
+
+        // (b2 - b1) x == a1 - a2 (mod n) has gcd(b2 - b1, n) solutions, if any.
+        let r = (b2 - b1).rem_euclid(n);
+        let s = (a1 - a2).rem_euclid(n);
+        let obj = algo_extended_euclidean(&r, n);
+        let d = obj.gcd.abs();
+        if r.is_zero() || !(&s % &d).is_zero() || d > BigInt::from(MAX_CANDIDATES) {
+            continue;
+        }
+        let n_d = n / &d;
+        let x0 = ((&s / &d) * obj.bezout_x).rem_euclid(&n_d);
+        let mut x = x0;
+        while &x < n {
+            if group.pow(g, &x) == *h {
+                return Some(x);
+            }
+            x += &n_d;
+        }
+    }
+    None
+}
+
+/// Pohlig–Hellman: some `x` with `g^x == h`, given the order of `g` as prime powers
+/// `order_factors = [(q_1, e_1), (q_2, e_2), ...]`.
+///
+/// For each `q^e`, `x mod q^e` is found digit by digit in base `q`, each digit being
+/// a discrete log in the subgroup of order `q`, solved by `bsgs`.
+/// The residues are then combined by `crt`. Returns `None` if `h` is not in the
+/// group generated by `g`.
+pub fn pohlig_hellman<G: CyclicGroup>(
+    group: &G,
+    g: &G::Elem,
+    h: &G::Elem,
+    order_factors: &[(BigInt, u32)],
+) -> Option<BigInt> {
+    let order: BigInt = order_factors
+        .iter()
+        .map(|(q, e)| pow(q.clone(), *e as usize))
+        .product();
+    let mut residues: Vec<BigInt> = Vec::new();
+    let mut moduli: Vec<BigInt> = Vec::new();
+    for (q, e) in order_factors.iter() {
+        let q_e = pow(q.clone(), *e as usize);
+        let cofactor = &order / &q_e;
+        let g_q = group.pow(g, &cofactor); // order q^e
+        let h_q = group.pow(h, &cofactor);
+        let gamma = group.pow(&g_q, &pow(q.clone(), *e as usize - 1)); // order q
+
+        // x_k == x (mod q^k), then h_q g_q^{-x_k} has order q^{e-k},
+        // and raising it to q^{e-k-1} exposes the next digit.
+        let mut x_k = const_0();
+        let mut q_k = const_1();
+        for k in 0..*e {
+            let rest = group.op(&h_q, &group.inv(&group.pow(&g_q, &x_k)));
+            let h_k = group.pow(&rest, &pow(q.clone(), (*e - k - 1) as usize));
+            let digit = bsgs(group, &gamma, &h_k, q)?;
+            x_k += digit * &q_k;
+            q_k *= q;
+        }
+        residues.push(x_k);
+        moduli.push(q_e);
+    }
+    let x = crt(&residues, &moduli).ok()?.x;
+    (group.pow(g, &x) == *h).then_some(x)
+}
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+
+use crate::prelude::*;
+use crate::{algo_extended_euclidean, crt, modinv, modpow};
+use num_bigint::RandBigInt;
+use rand::{CryptoRng, RngCore};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    /// `p - 1 = 2^5 3^3 5 7^2 11 13 17^2 23 29 43 61 83`, with primitive root 19.
+    fn smooth_group() -> (MulModGroup, BigInt, Vec<(BigInt, u32)>) {
+        let p = BigInt::from(1270328155698682081u64);
+        let factors = [
+            (2, 5),
+            (3, 3),
+            (5, 1),
+            (7, 2),
+            (11, 1),
+            (13, 1),
+            (17, 2),
+            (23, 1),
+            (29, 1),
+            (43, 1),
+            (61, 1),
+            (83, 1),
+        ];
+        let factors = factors
+            .iter()
+            .map(|(q, e)| (BigInt::from(*q), *e))
+            .collect();
+        (MulModGroup::new(&p), BigInt::from(19), factors)
+    }
+
+    #[test]
+    fn test_bsgs() {
+        let (group, g, _) = smooth_group();
+        for x in [0u64, 1, 2, 99, 1000, 65535, 1145141] {
+            let x = BigInt::from(x);
+            let h = group.pow(&g, &x);
+            assert_eq!(bsgs(&group, &g, &h, &BigInt::from(1145142)), Some(x));
+        }
+        let h = group.pow(&g, &BigInt::from(1145142));
+        assert_eq!(bsgs(&group, &g, &h, &BigInt::from(1145142)), None);
+        assert_eq!(
+            bsgs(&group, &g, &h, &BigInt::from(1145143)),
+            Some(BigInt::from(1145142))
+        );
+    }
+
+    #[test]
+    fn test_pollard_rho() {
+        // Quadratic residues modulo the safe prime 2q + 1, a subgroup of prime order q.
+        let q = BigInt::from(16777259);
+        let group = MulModGroup::new(&(&q * 2 + 1));
+        let g = BigInt::from(4);
+        let mut rng = StdRng::seed_from_u64(1919810);
+        for x in [BigInt::from(114514), &q - 1, &q / 3] {
+            let h = group.pow(&g, &x);
+            assert_eq!(pollard_rho_with_rng(&group, &g, &h, &q, &mut rng), Some(x));
+        }
+        // -1 is a non-residue as p == 3 (mod 4), outside the subgroup.
+        let h = group.modulus() - 1;
+        assert_eq!(pollard_rho_with_rng(&group, &g, &h, &q, &mut rng), None);
+    }
+
+    #[test]
+    fn test_pohlig_hellman() {
+        let (group, g, factors) = smooth_group();
+        let p_minus_1 = group.modulus() - 1;
+        for x in [
+            BigInt::from(0),
+            BigInt::from(114514),
+            &p_minus_1 - 1,
+            &p_minus_1 / 7,
+        ] {
+            let h = group.pow(&g, &x);
+            assert_eq!(pohlig_hellman(&group, &g, &h, &factors), Some(x));
+        }
+        // In the subgroup of order (p - 1) / 17^2, nothing reaches g itself.
+        let g2 = group.pow(&g, &BigInt::from(289));
+        let mut sub_factors = factors.clone();
+        sub_factors.retain(|(q, _)| q != &BigInt::from(17));
+        assert_eq!(pohlig_hellman(&group, &g2, &g, &sub_factors), None);
+    }
+}
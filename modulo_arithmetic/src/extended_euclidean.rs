@@ -71,12 +71,12 @@ pub fn moddiv(a: &BigInt, b: &BigInt, p: &BigInt) -> BigInt {
     if b.is_one() {
         return a.clone();
     }
-    let obj_bp = algo_extended_euclidean(&b, &p);
+    let obj_bp = algo_extended_euclidean(&b, p);
     let b_inv = obj_bp.bezout_x;
-    let prod = (a * b_inv).rem_euclid(p);
-    prod
+    (a * b_inv).rem_euclid(p)
 }
 
+/// Variable time in `a`, see `modinv_ct` for secret values modulo a prime.
 pub fn modinv(a: &BigInt, p: &BigInt) -> BigInt {
     let one = const_1();
     assert!(p > &one);
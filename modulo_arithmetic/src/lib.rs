@@ -1,7 +1,38 @@
+//! Modular arithmetic over `BigInt`.
+//!
+//! # Secret inputs
+//!
+//! Most functions here branch and allocate according to their values, and leak them through timing.
+//! They are fine for public data: moduli, public keys, ciphertexts, verification.
+//! * Variable time: `modpow`, `modinv`, `moddiv`, `algo_extended_euclidean`, `crt`,
+//!   `MontgomeryCtx::{pow, modpow}`, `CrtModPow`, `ModInt`, `jacobi`, `modsqrt` and the primality tests.
+//!   Prime generation runs them on secret candidates, so it should not be timed by an adversary.
+//! * Constant time in the secret operand: `MontgomeryCtx::{mul, square, pow_ct, inv_ct}`,
+//!   and `modpow_ct` / `modinv_ct` built on them. Use these for secret exponents, keys and nonces.
+//!
+//! Converting to and from `BigInt` at the boundary still leaks the bit length of the value,
+//! as `BigInt` strips leading zeros.
+
 mod extended_euclidean;
 pub use extended_euclidean::*;
 mod modpow;
 pub use modpow::*;
+mod montgomery;
+pub use montgomery::*;
+mod crt;
+pub use crt::*;
+mod sqrt;
+pub use sqrt::*;
+mod prime;
+pub use prime::*;
+mod consttime;
+pub use consttime::*;
+mod batch;
+pub use batch::*;
+mod modint;
+pub use modint::*;
+mod dlog;
+pub use dlog::*;
 
 pub mod prelude {
     pub fn const_0() -> BigInt {
@@ -0,0 +1,442 @@
+//! Residues that carry their modulus, so that every operation reduces by itself.
+//!
+//! * `ModInt` shares a runtime modulus through an `Arc`, e.g. a Paillier `n^2`.
+//!   Mixing two moduli in one operation panics.
+//! * `StaticModInt<M>` fixes the modulus in the type, e.g. `Secp256k1ModInt`,
+//!   so mixing moduli does not even compile.
+//!
+//! Both are canonical, `0 <= value < modulus`, and serialize as such.
+//! Like `modpow` and `modinv`, they are variable time, see the crate docs.
+
+/// `value mod modulus`, with `+ - * /`, negation, `pow` and `inv`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(into = "ModIntRepr", try_from = "ModIntRepr")]
+pub struct ModInt {
+    value: BigInt,
+    modulus: Arc<BigInt>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ModIntRepr {
+    value: BigInt,
+    modulus: BigInt,
+}
+
+impl ModInt {
+    /// `value mod modulus`, for any `value` and `modulus > 1`.
+    pub fn new(value: BigInt, modulus: &Arc<BigInt>) -> Self {
+        assert!(modulus.as_ref() > &const_1(), "modulus must be > 1");
+        Self {
+            value: value.rem_euclid(modulus),
+            modulus: modulus.clone(),
+        }
+    }
+
+    pub fn zero(modulus: &Arc<BigInt>) -> Self {
+        Self::new(const_0(), modulus)
+    }
+
+    pub fn one(modulus: &Arc<BigInt>) -> Self {
+        Self::new(const_1(), modulus)
+    }
+
+    /// Same modulus as `self`.
+    pub fn with_value(&self, value: BigInt) -> Self {
+        Self::new(value, &self.modulus)
+    }
+
+    /// The canonical representative, in `[0, modulus)`.
+    pub fn value(&self) -> &BigInt {
+        &self.value
+    }
+
+    pub fn modulus(&self) -> &Arc<BigInt> {
+        &self.modulus
+    }
+
+    pub fn into_value(self) -> BigInt {
+        self.value
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.value.is_zero()
+    }
+
+    /// `self^exp`. A negative `exp` needs `self` invertible.
+    pub fn pow(&self, exp: &BigInt) -> Self {
+        self.with_value(modpow(&self.value, exp, &self.modulus))
+    }
+
+    /// Panics if `self` is not invertible.
+    pub fn inv(&self) -> Self {
+        self.with_value(modinv(&self.value, &self.modulus))
+    }
+
+    fn check_modulus(&self, rhs: &Self) {
+        assert!(
+            Arc::ptr_eq(&self.modulus, &rhs.modulus) || self.modulus == rhs.modulus,
+            "ModInt operands have different moduli"
+        );
+    }
+
+    fn add_impl(&self, rhs: &Self) -> Self {
+        self.check_modulus(rhs);
+        self.with_value(&self.value + &rhs.value)
+    }
+
+    fn sub_impl(&self, rhs: &Self) -> Self {
+        self.check_modulus(rhs);
+        self.with_value(&self.value - &rhs.value)
+    }
+
+    fn mul_impl(&self, rhs: &Self) -> Self {
+        self.check_modulus(rhs);
+        self.with_value(&self.value * &rhs.value)
+    }
+
+    fn div_impl(&self, rhs: &Self) -> Self {
+        self.mul_impl(&rhs.inv())
+    }
+
+    fn neg_impl(&self) -> Self {
+        self.with_value(-&self.value)
+    }
+}
+
+impl From<ModInt> for ModIntRepr {
+    fn from(x: ModInt) -> Self {
+        Self {
+            value: x.value,
+            modulus: x.modulus.as_ref().clone(),
+        }
+    }
+}
+
+impl TryFrom<ModIntRepr> for ModInt {
+    type Error = String;
+
+    fn try_from(repr: ModIntRepr) -> Result<Self, Self::Error> {
+        if repr.modulus <= const_1() {
+            return Err(format!("modulus {} is not > 1", repr.modulus));
+        }
+        if repr.value.is_negative() || repr.value >= repr.modulus {
+            return Err(format!("value {} is not reduced", repr.value));
+        }
+        Ok(Self {
+            value: repr.value,
+            modulus: Arc::new(repr.modulus),
+        })
+    }
+}
+
+/// A modulus fixed by a type, e.g. `Secp256k1Order`.
+pub trait StaticModulus {
+    fn modulus() -> &'static BigInt;
+}
+
+/// The order of the secp256k1 group, i.e. `const_secp256k1_order()`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Secp256k1Order;
+
+impl StaticModulus for Secp256k1Order {
+    fn modulus() -> &'static BigInt {
+        static ORDER: OnceLock<BigInt> = OnceLock::new();
+        ORDER.get_or_init(const_secp256k1_order)
+    }
+}
+
+/// Integers modulo the secp256k1 group order.
+pub type Secp256k1ModInt = StaticModInt<Secp256k1Order>;
+
+/// `value mod M::modulus()`, with the same operations as `ModInt`.
+/// Serializes as the bare value.
+#[derive(Serialize, Deserialize)]
+#[serde(into = "BigInt", try_from = "BigInt")]
+pub struct StaticModInt<M: StaticModulus> {
+    value: BigInt,
+    _modulus: PhantomData<M>,
+}
+
+impl<M: StaticModulus> StaticModInt<M> {
+    /// `value mod M::modulus()`, for any `value`.
+    pub fn new(value: BigInt) -> Self {
+        Self {
+            value: value.rem_euclid(M::modulus()),
+            _modulus: PhantomData,
+        }
+    }
+
+    pub fn zero() -> Self {
+        Self::new(const_0())
+    }
+
+    pub fn one() -> Self {
+        Self::new(const_1())
+    }
+
+    /// The canonical representative, in `[0, M::modulus())`.
+    pub fn value(&self) -> &BigInt {
+        &self.value
+    }
+
+    pub fn modulus() -> &'static BigInt {
+        M::modulus()
+    }
+
+    pub fn into_value(self) -> BigInt {
+        self.value
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.value.is_zero()
+    }
+
+    /// `self^exp`. A negative `exp` needs `self` invertible.
+    pub fn pow(&self, exp: &BigInt) -> Self {
+        Self::new(modpow(&self.value, exp, M::modulus()))
+    }
+
+    /// Panics if `self` is not invertible.
+    pub fn inv(&self) -> Self {
+        Self::new(modinv(&self.value, M::modulus()))
+    }
+
+    fn add_impl(&self, rhs: &Self) -> Self {
+        Self::new(&self.value + &rhs.value)
+    }
+
+    fn sub_impl(&self, rhs: &Self) -> Self {
+        Self::new(&self.value - &rhs.value)
+    }
+
+    fn mul_impl(&self, rhs: &Self) -> Self {
+        Self::new(&self.value * &rhs.value)
+    }
+
+    fn div_impl(&self, rhs: &Self) -> Self {
+        self.mul_impl(&rhs.inv())
+    }
+
+    fn neg_impl(&self) -> Self {
+        Self::new(-&self.value)
+    }
+}
+
+// Derives would require `M: Clone` etc., though `M` is only a marker.
+impl<M: StaticModulus> Clone for StaticModInt<M> {
+    fn clone(&self) -> Self {
+        Self {
+            value: self.value.clone(),
+            _modulus: PhantomData,
+        }
+    }
+}
+
+impl<M: StaticModulus> PartialEq for StaticModInt<M> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<M: StaticModulus> Eq for StaticModInt<M> {}
+
+impl<M: StaticModulus> Hash for StaticModInt<M> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.value.hash(state);
+    }
+}
+
+impl<M: StaticModulus> Debug for StaticModInt<M> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "StaticModInt({})", self.value)
+    }
+}
+
+impl<M: StaticModulus> From<u64> for StaticModInt<M> {
+    fn from(x: u64) -> Self {
+        Self::new(BigInt::from(x))
+    }
+}
+
+impl<M: StaticModulus> From<StaticModInt<M>> for BigInt {
+    fn from(x: StaticModInt<M>) -> Self {
+        x.value
+    }
+}
+
+impl<M: StaticModulus> TryFrom<BigInt> for StaticModInt<M> {
+    type Error = String;
+
+    fn try_from(value: BigInt) -> Result<Self, Self::Error> {
+        if value.is_negative() || &value >= M::modulus() {
+            return Err(format!("value {} is not reduced", value));
+        }
+        Ok(Self::new(value))
+    }
+}
+
+impl Display for ModInt {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (mod {})", self.value, self.modulus)
+    }
+}
+
+impl<M: StaticModulus> Display for StaticModInt<M> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.value)
+    }
+}
+
+/// `a op b`, `a op= b` for all combinations of owned and borrowed operands.
+macro_rules! impl_binop {
+    ([$($gen:tt)*] $T:ty, $Op:ident, $op:ident, $OpAssign:ident, $op_assign:ident, $imp:ident) => {
+        impl<$($gen)*> $Op<&$T> for &$T {
+            type Output = $T;
+            fn $op(self, rhs: &$T) -> $T {
+                self.$imp(rhs)
+            }
+        }
+        impl<$($gen)*> $Op<$T> for &$T {
+            type Output = $T;
+            fn $op(self, rhs: $T) -> $T {
+                self.$imp(&rhs)
+            }
+        }
+        impl<$($gen)*> $Op<&$T> for $T {
+            type Output = $T;
+            fn $op(self, rhs: &$T) -> $T {
+                self.$imp(rhs)
+            }
+        }
+        impl<$($gen)*> $Op<$T> for $T {
+            type Output = $T;
+            fn $op(self, rhs: $T) -> $T {
+                self.$imp(&rhs)
+            }
+        }
+        impl<$($gen)*> $OpAssign<&$T> for $T {
+            fn $op_assign(&mut self, rhs: &$T) {
+                *self = self.$imp(rhs);
+            }
+        }
+        impl<$($gen)*> $OpAssign<$T> for $T {
+            fn $op_assign(&mut self, rhs: $T) {
+                *self = self.$imp(&rhs);
+            }
+        }
+    };
+}
+
+macro_rules! impl_arith {
+    ([$($gen:tt)*] $T:ty) => {
+        impl_binop!([$($gen)*] $T, Add, add, AddAssign, add_assign, add_impl);
+        impl_binop!([$($gen)*] $T, Sub, sub, SubAssign, sub_assign, sub_impl);
+        impl_binop!([$($gen)*] $T, Mul, mul, MulAssign, mul_assign, mul_impl);
+        impl_binop!([$($gen)*] $T, Div, div, DivAssign, div_assign, div_impl);
+
+        impl<$($gen)*> Neg for &$T {
+            type Output = $T;
+            fn neg(self) -> $T {
+                self.neg_impl()
+            }
+        }
+        impl<$($gen)*> Neg for $T {
+            type Output = $T;
+            fn neg(self) -> $T {
+                self.neg_impl()
+            }
+        }
+    };
+}
+
+impl_arith!([] ModInt);
+impl_arith!([M: StaticModulus] StaticModInt<M>);
+
+use std::fmt::{self, Debug, Display, Formatter};
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use std::sync::{Arc, OnceLock};
+
+use crate::prelude::*;
+use crate::{modinv, modpow};
+use serde::{Deserialize, Serialize};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_modint() {
+        let p = Arc::new(BigInt::from(7919));
+        let a = ModInt::new(BigInt::from(-1145), &p);
+        let b = ModInt::new(BigInt::from(14191981), &p);
+        assert_eq!(a.value(), &BigInt::from(7919 - 1145));
+        assert_eq!(b.value(), &BigInt::from(14191981 % 7919));
+
+        assert_eq!(&a + &b, a.with_value(a.value() + b.value()));
+        assert_eq!(&a - &b, a.with_value(a.value() - b.value()));
+        assert_eq!(&a * &b, a.with_value(a.value() * b.value()));
+        assert_eq!(&(&a / &b) * &b, a);
+        assert_eq!(&a + &(-&a), ModInt::zero(&p));
+        assert_eq!(&a * &a.inv(), ModInt::one(&p));
+        assert_eq!(a.pow(&BigInt::from(7918)), ModInt::one(&p)); // Fermat
+        assert_eq!(a.pow(&BigInt::from(-1)), a.inv());
+        assert!((-ModInt::zero(&p)).is_zero());
+
+        let mut c = a.clone();
+        c += &b;
+        c *= b.clone();
+        c -= &a;
+        c /= a.clone();
+        assert_eq!(c, (&(&(&a + &b) * &b) - &a) / &a);
+
+        // Equal moduli from different `Arc`s mix fine.
+        let q = Arc::new(BigInt::from(7919));
+        assert_eq!(&a + &ModInt::one(&q), &a + &ModInt::one(&p));
+    }
+
+    #[test]
+    #[should_panic(expected = "different moduli")]
+    fn test_modint_mixed_moduli() {
+        let p = Arc::new(BigInt::from(7919));
+        let q = Arc::new(BigInt::from(7907));
+        let _ = ModInt::one(&p) + ModInt::one(&q);
+    }
+
+    #[test]
+    fn test_static_modint() {
+        let n = const_secp256k1_order();
+        let a = Secp256k1ModInt::new(-BigInt::from(114514));
+        let b = Secp256k1ModInt::from(1919810);
+        assert_eq!(a.value(), &(&n - 114514));
+        assert_eq!((&a * &b).value(), &(a.value() * b.value()).rem_euclid(&n));
+        assert_eq!(&(&a / &b) * &b, a);
+        assert_eq!(a.clone() - a.clone(), Secp256k1ModInt::zero());
+        assert_eq!(-(-a.clone()), a);
+        assert_eq!(a.pow(&(&n - 1)), Secp256k1ModInt::one());
+        assert_eq!(Secp256k1ModInt::modulus(), &n);
+    }
+
+    #[test]
+    fn test_modint_serde() {
+        let p = Arc::new(const_mersenne12());
+        let a = ModInt::new(BigInt::from(-1), &p);
+        let json = serde_json::to_string(&a).unwrap();
+        assert_eq!(serde_json::from_str::<ModInt>(&json).unwrap(), a);
+
+        let b = Secp256k1ModInt::new(-BigInt::from(1));
+        let json = serde_json::to_string(&b).unwrap();
+        assert_eq!(serde_json::from_str::<Secp256k1ModInt>(&json).unwrap(), b);
+
+        // Non-canonical encodings are rejected.
+        let bad = serde_json::to_string(&ModIntRepr {
+            value: const_mersenne12(),
+            modulus: const_mersenne12(),
+        })
+        .unwrap();
+        assert!(serde_json::from_str::<ModInt>(&bad).is_err());
+        let bad = serde_json::to_string(&const_secp256k1_order()).unwrap();
+        assert!(serde_json::from_str::<Secp256k1ModInt>(&bad).is_err());
+    }
+}
@@ -1,5 +1,7 @@
 /// Let $ e = \sum_{i=0}^{n-1} d_i \cdot 2^i \mod p $, then
 /// $ y = \prod_{i=0}^{n-1} (b^2^i)^{d_i} \mod p $.
+///
+/// Variable time in `exp`, see `modpow_ct` for secret exponents.
 #[allow(non_snake_case)]
 pub fn modpow(base: &BigInt, exp: &BigInt, p: &BigInt) -> BigInt {
     let mut base = base.rem_euclid(p);
@@ -33,7 +35,7 @@ mod tests {
         let mut base = base.rem_euclid(p);
         let mut exp = exp.clone();
         if exp.is_negative() {
-            base = crate::modinv(&base, &p);
+            base = crate::modinv(&base, p);
             exp = -exp;
         }
         let mut y = const_1();
@@ -0,0 +1,303 @@
+/// Precomputed context for Montgomery arithmetic modulo an odd `p`.
+///
+/// With `R = 2^(64 * limbs)`, an element `x` is kept as `x * R mod p` in fixed-size limbs,
+/// and `mont_mul(a, b) = a * b * R^{-1} mod p` needs no division at all,
+/// only word multiplications and one conditional subtraction.
+/// Worth it when many operations share a modulus, e.g. exponentiation.
+#[derive(Clone, Debug)]
+pub struct MontgomeryCtx {
+    p: BigInt,
+    p_limbs: Vec<u64>,
+    p_inv_neg: u64,      // -p^{-1} mod 2^64
+    r2: MontgomeryElem,  // R^2 mod p, so that to_montgomery(x) == mont_mul(x, R^2)
+    one: MontgomeryElem, // R mod p
+}
+
+/// `x * R mod p`, little-endian limbs. Only meaningful with the context that made it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MontgomeryElem(Vec<u64>);
+
+impl MontgomeryCtx {
+    pub fn new(p: &BigInt) -> Self {
+        assert!(
+            p > &const_1() && p.bit(0),
+            "Montgomery modulus must be odd and > 1"
+        );
+        let p_limbs = to_limbs(p, limb_count(p));
+        let n = p_limbs.len();
+
+        // Newton iteration for p^{-1} mod 2^64, doubling correct bits each step.
+        let mut inv: u64 = 1;
+        for _ in 0..6 {
+            inv = inv.wrapping_mul(2u64.wrapping_sub(p_limbs[0].wrapping_mul(inv)));
+        }
+
+        let r = const_1() << (64 * n);
+        let one = MontgomeryElem(to_limbs(&r.rem_euclid(p), n));
+        let r2 = MontgomeryElem(to_limbs(&(&r * &r).rem_euclid(p), n));
+        Self {
+            p: p.clone(),
+            p_limbs,
+            p_inv_neg: inv.wrapping_neg(),
+            r2,
+            one,
+        }
+    }
+
+    pub fn modulus(&self) -> &BigInt {
+        &self.p
+    }
+
+    pub fn to_montgomery(&self, x: &BigInt) -> MontgomeryElem {
+        let x = MontgomeryElem(to_limbs(&x.rem_euclid(&self.p), self.p_limbs.len()));
+        self.mul(&x, &self.r2)
+    }
+
+    pub fn from_montgomery(&self, x: &MontgomeryElem) -> BigInt {
+        let mut one = vec![0u64; self.p_limbs.len()];
+        one[0] = 1;
+        from_limbs(&self.mul(x, &MontgomeryElem(one)).0)
+    }
+
+    /// `1` in Montgomery form, i.e. `R mod p`.
+    pub fn one(&self) -> MontgomeryElem {
+        self.one.clone()
+    }
+
+    /// `a * b * R^{-1} mod p`, by coarsely integrated operand scanning (CIOS).
+    pub fn mul(&self, a: &MontgomeryElem, b: &MontgomeryElem) -> MontgomeryElem {
+        let n = self.p_limbs.len();
+        let (a, b, p) = (&a.0, &b.0, &self.p_limbs);
+        let mut t = vec![0u64; n + 2];
+        for b_i in b.iter() {
+            // t += a * b_i
+            let mut carry = 0u64;
+            for j in 0..n {
+                (t[j], carry) = mac(t[j], a[j], *b_i, carry);
+            }
+            let (sum, c) = t[n].overflowing_add(carry);
+            t[n] = sum;
+            t[n + 1] = c as u64;
+
+            // t = (t + m * p) / 2^64, with m chosen to zero the lowest limb.
+            let m = t[0].wrapping_mul(self.p_inv_neg);
+            let (_, mut carry) = mac(t[0], m, p[0], 0);
+            for j in 1..n {
+                (t[j - 1], carry) = mac(t[j], m, p[j], carry);
+            }
+            let (sum, c) = t[n].overflowing_add(carry);
+            t[n - 1] = sum;
+            t[n] = t[n + 1] + c as u64;
+        }
+        // t < 2p, one subtraction at most. Always computed and selected by mask,
+        // so that the timing does not depend on the operands.
+        let mut d = vec![0u64; n];
+        let mut borrow = 0u64;
+        for j in 0..n {
+            let (d1, b1) = t[j].overflowing_sub(p[j]);
+            let (d2, b2) = d1.overflowing_sub(borrow);
+            d[j] = d2;
+            borrow = (b1 | b2) as u64;
+        }
+        // t >= p iff the top limb is set or the low limbs did not borrow.
+        let mask = black_box(0u64.wrapping_sub(t[n] | (borrow ^ 1)));
+        for j in 0..n {
+            t[j] = (d[j] & mask) | (t[j] & !mask);
+        }
+        t.truncate(n);
+        MontgomeryElem(t)
+    }
+
+    pub fn square(&self, a: &MontgomeryElem) -> MontgomeryElem {
+        self.mul(a, a)
+    }
+
+    /// `base^exp` for `exp >= 0`, by sliding-window exponentiation.
+    ///
+    /// Only odd powers `base^1, base^3, ..., base^(2^w - 1)` are precomputed,
+    /// and each window of up to `w` bits ending in a 1 costs one multiplication.
+    pub fn pow(&self, base: &MontgomeryElem, exp: &BigInt) -> MontgomeryElem {
+        assert!(!exp.is_negative(), "negative exponent, see `modpow`");
+        let bits = exp.bits() as usize;
+        if bits == 0 {
+            return self.one();
+        }
+        let w = match bits {
+            0..=64 => 3,
+            65..=512 => 4,
+            513..=2048 => 5,
+            _ => 6,
+        };
+
+        let base_sq = self.square(base);
+        let mut odd_powers = vec![base.clone()];
+        for i in 1..(1 << (w - 1)) {
+            odd_powers.push(self.mul(&odd_powers[i - 1], &base_sq));
+        }
+
+        let mut y = self.one();
+        let mut i = bits as isize - 1;
+        while i >= 0 {
+            if !exp.bit(i as u64) {
+                y = self.square(&y);
+                i -= 1;
+                continue;
+            }
+            // Longest window exp[l..=i] of at most `w` bits that ends in a 1.
+            let mut l = (i - w as isize + 1).max(0);
+            while !exp.bit(l as u64) {
+                l += 1;
+            }
+            let mut val = 0usize;
+            for j in (l..=i).rev() {
+                y = self.square(&y);
+                val = (val << 1) | exp.bit(j as u64) as usize;
+            }
+            y = self.mul(&y, &odd_powers[val >> 1]);
+            i = l - 1;
+        }
+        y
+    }
+
+    /// `base^exp` in constant time for `0 <= exp < 2^exp_bits`, by a Montgomery ladder.
+    ///
+    /// Every one of the `exp_bits` steps does one multiplication, one squaring
+    /// and two masked swaps, whatever the bit. Only `exp_bits` is public,
+    /// e.g. the bit length of the group order.
+    pub fn pow_ct(&self, base: &MontgomeryElem, exp: &BigInt, exp_bits: u64) -> MontgomeryElem {
+        assert!(
+            !exp.is_negative() && exp.bits() <= exp_bits,
+            "exponent out of range"
+        );
+        let exp_limbs = to_limbs(exp, (exp_bits as usize).div_ceil(64));
+        // Invariant: r1 == r0 * base.
+        let mut r0 = self.one();
+        let mut r1 = base.clone();
+        for i in (0..exp_bits as usize).rev() {
+            let bit = (exp_limbs[i / 64] >> (i % 64)) & 1;
+            cswap(&mut r0, &mut r1, bit);
+            r1 = self.mul(&r0, &r1);
+            r0 = self.square(&r0);
+            cswap(&mut r0, &mut r1, bit);
+        }
+        r0
+    }
+
+    /// `a^{-1}` in constant time by Fermat's little theorem, `a^(p-2)`.
+    /// Requires a prime modulus, and maps 0 to 0.
+    pub fn inv_ct(&self, a: &MontgomeryElem) -> MontgomeryElem {
+        self.pow_ct(a, &(&self.p - 2u8), self.p.bits())
+    }
+
+    /// Same as `crate::modpow`, with the modulus of this context.
+    pub fn modpow(&self, base: &BigInt, exp: &BigInt) -> BigInt {
+        let mut base = base.rem_euclid(&self.p);
+        let mut exp = exp.clone();
+        if exp.is_negative() {
+            base = crate::modinv(&base, &self.p);
+            exp = -exp;
+        }
+        let y = self.pow(&self.to_montgomery(&base), &exp);
+        self.from_montgomery(&y)
+    }
+}
+
+/// Swap `a` and `b` iff `choice == 1`, without branching on `choice`.
+fn cswap(a: &mut MontgomeryElem, b: &mut MontgomeryElem, choice: u64) {
+    let mask = black_box(0u64.wrapping_sub(choice));
+    for (x, y) in a.0.iter_mut().zip(b.0.iter_mut()) {
+        let t = (*x ^ *y) & mask;
+        *x ^= t;
+        *y ^= t;
+    }
+}
+
+/// `a + b * c + carry`, as (low, high) words.
+fn mac(a: u64, b: u64, c: u64, carry: u64) -> (u64, u64) {
+    let t = a as u128 + b as u128 * c as u128 + carry as u128;
+    (t as u64, (t >> 64) as u64)
+}
+
+fn limb_count(x: &BigInt) -> usize {
+    (x.bits() as usize).div_ceil(64)
+}
+
+fn to_limbs(x: &BigInt, n: usize) -> Vec<u64> {
+    let mut limbs: Vec<u64> = x.iter_u64_digits().collect();
+    limbs.resize(n, 0);
+    limbs
+}
+
+fn from_limbs(limbs: &[u64]) -> BigInt {
+    let digits: Vec<u32> = limbs
+        .iter()
+        .flat_map(|limb| [*limb as u32, (*limb >> 32) as u32])
+        .collect();
+    BigInt::from_biguint(num_bigint::Sign::Plus, BigUint::new(digits))
+}
+
+use std::hint::black_box;
+
+use crate::prelude::*;
+use num_bigint::BigUint;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modpow;
+
+    #[test]
+    fn test_montgomery_mul() {
+        for p in [
+            BigInt::from(1919),
+            const_mersenne12(),
+            const_secp256k1_order(),
+            (const_1() << 2048) - 159, // some odd 2048-bit modulus
+        ] {
+            let ctx = MontgomeryCtx::new(&p);
+            let a = (&p * 2 / 3) + 17;
+            let b: BigInt = 3 - &p / 5;
+            let am = ctx.to_montgomery(&a);
+            let bm = ctx.to_montgomery(&b);
+            assert_eq!(ctx.from_montgomery(&am), a.rem_euclid(&p));
+            assert_eq!(
+                ctx.from_montgomery(&ctx.mul(&am, &bm)),
+                (&a * &b).rem_euclid(&p)
+            );
+            assert_eq!(
+                ctx.from_montgomery(&ctx.square(&am)),
+                (&a * &a).rem_euclid(&p)
+            );
+            assert_eq!(ctx.from_montgomery(&ctx.one()), const_1());
+        }
+    }
+
+    #[test]
+    fn test_montgomery_modpow() {
+        let p = BigInt::from(1919);
+        let ctx = MontgomeryCtx::new(&p);
+        for (base, exp) in [
+            (114, 514),
+            (-114, 514),
+            (514, -114),
+            (5, 0),
+            (0, 7),
+            (1918, 1),
+        ] {
+            let (base, exp) = (BigInt::from(base), BigInt::from(exp));
+            assert_eq!(ctx.modpow(&base, &exp), modpow(&base, &exp, &p));
+        }
+
+        let p = (const_1() << 2048) - 159;
+        let ctx = MontgomeryCtx::new(&p);
+        let base = &p / 7 + 12345;
+        for exp in [
+            const_1() << 2047,
+            (const_1() << 2048) - 1,
+            &p - 2,
+            BigInt::from(0b1011_0111_0001u64),
+        ] {
+            assert_eq!(ctx.modpow(&base, &exp), modpow(&base, &exp, &p));
+        }
+    }
+}
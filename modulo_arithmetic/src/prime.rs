@@ -0,0 +1,304 @@
+/// Miller–Rabin with a single `base`: is `n` a strong probable prime to `base`?
+///
+/// Write `n - 1 = d * 2^s` with `d` odd. A prime `n` has either `base^d == 1`
+/// or `base^(d * 2^r) == -1` for some `0 <= r < s`, since `x^2 == 1` has no roots but `±1`.
+/// Requires odd `n > 2`.
+pub fn is_strong_probable_prime(n: &BigInt, base: &BigInt) -> bool {
+    assert!(n > &BigInt::from(2) && n.bit(0), "n must be odd and > 2");
+    let n_minus_1 = n - 1u8;
+    let s = n_minus_1.trailing_zeros().unwrap();
+    let d = &n_minus_1 >> s;
+    let mut x = modpow(base, &d, n);
+    if x.is_zero() {
+        return false; // base == 0 (mod n), no information
+    }
+    if x.is_one() || x == n_minus_1 {
+        return true;
+    }
+    for _ in 1..s {
+        x = (&x * &x).rem_euclid(n);
+        if x == n_minus_1 {
+            return true;
+        }
+        if x.is_one() {
+            return false;
+        }
+    }
+    false
+}
+
+/// Miller–Rabin with `rounds` random bases.
+/// A composite passes with probability at most `4^-rounds`.
+pub fn miller_rabin(n: &BigInt, rounds: usize) -> bool {
+    miller_rabin_with_rng(n, rounds, &mut rand::thread_rng())
+}
+
+/// Same as `miller_rabin`, drawing the bases from `rng`.
+pub fn miller_rabin_with_rng<R: CryptoRng + RngCore>(
+    n: &BigInt,
+    rounds: usize,
+    rng: &mut R,
+) -> bool {
+    if let Some(is_prime) = trial_division(n) {
+        return is_prime;
+    }
+    let (lo, hi) = (BigInt::from(2), n - 1u8);
+    (0..rounds).all(|_| is_strong_probable_prime(n, &rng.gen_bigint_range(&lo, &hi)))
+}
+
+/// Strong Lucas probable prime test with Selfridge's parameters:
+/// `D` is the first of `5, -7, 9, -11, ...` with `(D/n) == -1`, `P = 1` and `Q = (1 - D) / 4`.
+///
+/// Write `n + 1 = d * 2^s` with `d` odd. A prime `n` has either `U_d == 0`
+/// or `V_(d * 2^r) == 0` for some `0 <= r < s`. Requires odd `n > 2`.
+pub fn is_strong_lucas_probable_prime(n: &BigInt) -> bool {
+    assert!(n > &BigInt::from(2) && n.bit(0), "n must be odd and > 2");
+    if is_perfect_square(n) {
+        return false; // no suitable D would ever be found
+    }
+    let mut d = BigInt::from(5);
+    loop {
+        match jacobi(&d, n) {
+            -1 => break,
+            0 if d.abs() != *n => return false,
+            _ => {}
+        }
+        d = if d.is_positive() { -d - 2 } else { -d + 2 };
+    }
+    let q = ((const_1() - &d) / 4u8).rem_euclid(n);
+    let d = d.rem_euclid(n);
+
+    let n_plus_1 = n + 1u8;
+    let s = n_plus_1.trailing_zeros().unwrap();
+    let k = &n_plus_1 >> s;
+
+    // x / 2 (mod n) for odd n.
+    let half = |x: BigInt| -> BigInt {
+        if x.bit(0) {
+            (x + n) >> 1
+        } else {
+            x >> 1
+        }
+    };
+    // Binary Lucas chain for (U_k, V_k, Q^k), with P = 1:
+    // U_2j = U_j V_j, V_2j = V_j^2 - 2Q^j,
+    // U_(j+1) = (U_j + V_j) / 2, V_(j+1) = (D U_j + V_j) / 2.
+    let (mut u, mut v, mut q_k) = (const_1(), const_1(), q.clone());
+    for i in (0..k.bits() - 1).rev() {
+        u = (&u * &v).rem_euclid(n);
+        v = (&v * &v - &q_k * 2u8).rem_euclid(n);
+        q_k = (&q_k * &q_k).rem_euclid(n);
+        if k.bit(i) {
+            let u_next = half(&u + &v);
+            v = half(&d * &u + &v).rem_euclid(n);
+            u = u_next.rem_euclid(n);
+            q_k = (&q_k * &q).rem_euclid(n);
+        }
+    }
+    if u.is_zero() || v.is_zero() {
+        return true;
+    }
+    for _ in 1..s {
+        v = (&v * &v - &q_k * 2u8).rem_euclid(n);
+        if v.is_zero() {
+            return true;
+        }
+        q_k = (&q_k * &q_k).rem_euclid(n);
+    }
+    false
+}
+
+/// Baillie–PSW: trial division, Miller–Rabin to base 2, then a strong Lucas test.
+///
+/// Deterministic, and no composite is known to pass, while the pseudoprimes of the
+/// two tests seem to avoid each other. Proven correct below `2^64`.
+pub fn is_prime(n: &BigInt) -> bool {
+    if let Some(is_prime) = trial_division(n) {
+        return is_prime;
+    }
+    is_strong_probable_prime(n, &BigInt::from(2)) && is_strong_lucas_probable_prime(n)
+}
+
+/// A uniformly random prime of exactly `bits` bits, with the top two bits set,
+/// so that the product of two such primes has exactly `2 * bits` bits.
+pub fn random_prime(bits: u64) -> BigInt {
+    random_prime_with_rng(bits, &mut rand::thread_rng())
+}
+
+/// Same as `random_prime`, drawing the candidates from `rng`.
+pub fn random_prime_with_rng<R: CryptoRng + RngCore>(bits: u64, rng: &mut R) -> BigInt {
+    assert!(bits >= 2, "need at least 2 bits");
+    loop {
+        let n = random_candidate(bits, rng);
+        if is_prime(&n) {
+            return n;
+        }
+    }
+}
+
+/// A random safe prime `p = 2q + 1` of exactly `bits` bits, with `q` also prime
+/// and the top two bits of `p` set. The multiplicative group mod `p` then has a
+/// subgroup of prime order `q`, the quadratic residues.
+pub fn random_safe_prime(bits: u64) -> BigInt {
+    random_safe_prime_with_rng(bits, &mut rand::thread_rng())
+}
+
+/// Same as `random_safe_prime`, drawing the candidates from `rng`.
+pub fn random_safe_prime_with_rng<R: CryptoRng + RngCore>(bits: u64, rng: &mut R) -> BigInt {
+    assert!(bits >= 3, "need at least 3 bits");
+    loop {
+        let q = random_candidate(bits - 1, rng);
+        let p: BigInt = &q * 2u8 + 1u8;
+        // Sieve both before any exponentiation, most candidates die here.
+        if trial_division(&q) == Some(false) || trial_division(&p) == Some(false) {
+            continue;
+        }
+        // Cheap filter: a prime p has 2^(2q) == 1, so 2^q == ±1 (mod p).
+        let t = modpow(&BigInt::from(2), &q, &p);
+        if !t.is_one() && t != &p - 1u8 {
+            continue;
+        }
+        if is_prime(&q) && is_prime(&p) {
+            return p;
+        }
+    }
+}
+
+/// Odd, with the top two bits set. For `bits == 2` that is just 3.
+fn random_candidate<R: CryptoRng + RngCore>(bits: u64, rng: &mut R) -> BigInt {
+    let mut n = BigInt::from(rng.gen_biguint(bits));
+    n.set_bit(bits - 1, true);
+    n.set_bit(bits - 2, true);
+    n.set_bit(0, true);
+    n
+}
+
+fn is_perfect_square(n: &BigInt) -> bool {
+    let r = n.sqrt();
+    &(&r * &r) == n
+}
+
+const SIEVE_LIMIT: u32 = 1000;
+
+/// Primes below `SIEVE_LIMIT`.
+fn small_primes() -> &'static [u32] {
+    static PRIMES: OnceLock<Vec<u32>> = OnceLock::new();
+    PRIMES.get_or_init(|| {
+        let mut composite = vec![false; SIEVE_LIMIT as usize];
+        let mut primes = vec![];
+        for i in 2..SIEVE_LIMIT as usize {
+            if !composite[i] {
+                primes.push(i as u32);
+                for j in (i * i..SIEVE_LIMIT as usize).step_by(i) {
+                    composite[j] = true;
+                }
+            }
+        }
+        primes
+    })
+}
+
+/// `Some(answer)` if division by the small primes settles whether `n` is prime.
+fn trial_division(n: &BigInt) -> Option<bool> {
+    if n < &BigInt::from(2) {
+        return Some(false);
+    }
+    for r in small_primes() {
+        if n == &BigInt::from(*r) {
+            return Some(true);
+        }
+        if (n % *r).is_zero() {
+            return Some(false);
+        }
+    }
+    if n < &BigInt::from(SIEVE_LIMIT * SIEVE_LIMIT) {
+        return Some(true);
+    }
+    None
+}
+
+use std::sync::OnceLock;
+
+use crate::prelude::*;
+use crate::{jacobi, modpow};
+use num_bigint::RandBigInt;
+use rand::{CryptoRng, RngCore};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    fn sieve(limit: usize) -> Vec<bool> {
+        let mut is_prime = vec![true; limit];
+        is_prime[0] = false;
+        is_prime[1] = false;
+        for i in 2..limit {
+            if is_prime[i] {
+                for j in (i * i..limit).step_by(i) {
+                    is_prime[j] = false;
+                }
+            }
+        }
+        is_prime
+    }
+
+    #[test]
+    fn test_is_prime() {
+        let expected = sieve(20000);
+        for (n, expected) in expected.iter().enumerate() {
+            let n = BigInt::from(n);
+            assert_eq!(is_prime(&n), *expected, "{}", n);
+            assert_eq!(miller_rabin(&n, 10), *expected, "{}", n);
+            if n > BigInt::from(2) && n.bit(0) {
+                assert_eq!(
+                    is_strong_lucas_probable_prime(&n),
+                    *expected || [5459, 5777, 10877, 16109, 18971].contains(&n.to_i64().unwrap())
+                );
+            }
+        }
+
+        assert!(is_prime(&const_mersenne12()));
+        assert!(is_prime(&const_secp256k1_order()));
+        assert!(!is_prime(&(const_mersenne12() * const_secp256k1_order())));
+        assert!(!is_prime(&((const_1() << 128) + 1u8))); // F_7
+
+        // Strong pseudoprimes to base 2 and to many bases, caught by the Lucas test.
+        for n in [2047u64, 3215031751, 3825123056546413051] {
+            let n = BigInt::from(n);
+            assert!(is_strong_probable_prime(&n, &BigInt::from(2)));
+            assert!(!is_strong_lucas_probable_prime(&n));
+            assert!(!is_prime(&n));
+        }
+        // Carmichael numbers.
+        for n in [561u64, 41041, 825265, 321197185] {
+            assert!(!is_prime(&BigInt::from(n)));
+        }
+    }
+
+    #[test]
+    fn test_random_prime() {
+        let mut rng = StdRng::seed_from_u64(1145141919810);
+        for bits in [2, 3, 10, 64, 256] {
+            let p = random_prime_with_rng(bits, &mut rng);
+            assert_eq!(p.bits(), bits);
+            assert!(p.bit(bits - 2));
+            assert!(is_prime(&p));
+            assert!(miller_rabin_with_rng(&p, 20, &mut rng));
+        }
+
+        for bits in [3, 8, 64, 160] {
+            let p = random_safe_prime_with_rng(bits, &mut rng);
+            assert_eq!(p.bits(), bits);
+            assert!(is_prime(&p));
+            assert!(is_prime(&((&p - 1u8) >> 1)));
+        }
+    }
+
+    #[test]
+    fn test_random_prime_seeded() {
+        let prime = |seed: u64| random_prime_with_rng(128, &mut StdRng::seed_from_u64(seed));
+        assert_eq!(prime(42), prime(42));
+        assert_ne!(prime(42), prime(43));
+    }
+}
@@ -0,0 +1,168 @@
+/// Jacobi symbol `(a/n)` for odd `n > 0`, in `{-1, 0, 1}`.
+///
+/// Computed by quadratic reciprocity, without factoring `n`:
+/// * `(2/n) = -1` iff `n == 3, 5 (mod 8)`, and
+/// * `(a/n) = -(n/a)` iff `a == n == 3 (mod 4)`, for odd `a`.
+pub fn jacobi(a: &BigInt, n: &BigInt) -> i8 {
+    assert!(n.is_positive() && n.bit(0), "n must be odd and positive");
+    let mut a = a.rem_euclid(n);
+    let mut n = n.clone();
+    let mut sign: i8 = 1;
+    while !a.is_zero() {
+        let twos = a.trailing_zeros().unwrap();
+        a >>= twos;
+        let n_mod_8 = (&n % 8u8).to_u8().unwrap();
+        if twos % 2 == 1 && (n_mod_8 == 3 || n_mod_8 == 5) {
+            sign = -sign;
+        }
+        if (&a % 4u8).to_u8().unwrap() == 3 && n_mod_8 % 4 == 3 {
+            sign = -sign;
+        }
+        std::mem::swap(&mut a, &mut n);
+        a = a.rem_euclid(&n);
+    }
+    if n.is_one() {
+        sign
+    } else {
+        0
+    }
+}
+
+/// Legendre symbol `(a/p)` for an odd prime `p`:
+/// `1` if `a` is a non-zero square mod `p`, `-1` if not, and `0` if `p` divides `a`.
+///
+/// Equal to the Jacobi symbol, which is cheaper than Euler's criterion `a^((p-1)/2)`.
+pub fn legendre(a: &BigInt, p: &BigInt) -> i8 {
+    jacobi(a, p)
+}
+
+/// A square root of `a` modulo an odd prime `p`, i.e. `r` with `r^2 == a (mod p)`.
+/// Returns the smaller of `r` and `p - r`, or `None` if `a` is not a square.
+///
+/// * `p == 3 (mod 4)`: `r = a^((p+1)/4)`.
+/// * `p == 5 (mod 8)`: Atkin's formula, `v = (2a)^((p-5)/8)`, `i = 2a v^2`, `r = a v (i - 1)`.
+/// * Otherwise Tonelli–Shanks.
+pub fn modsqrt(a: &BigInt, p: &BigInt) -> Option<BigInt> {
+    assert!(p > &const_1() && p.bit(0), "p must be an odd prime");
+    let a = a.rem_euclid(p);
+    if a.is_zero() {
+        return Some(a);
+    }
+    if legendre(&a, p) != 1 {
+        return None;
+    }
+
+    let p_mod_8 = (p % 8u8).to_u8().unwrap();
+    let r = if p_mod_8 % 4 == 3 {
+        modpow(&a, &((p + 1u8) >> 2), p)
+    } else if p_mod_8 == 5 {
+        let two_a = (&a * 2u8).rem_euclid(p);
+        let v = modpow(&two_a, &((p - 5u8) >> 3), p);
+        let i = (&two_a * &v * &v).rem_euclid(p);
+        (&a * &v * (i - 1u8)).rem_euclid(p)
+    } else {
+        tonelli_shanks(&a, p)
+    };
+    debug_assert_eq!((&r * &r).rem_euclid(p), a);
+    Some(r.clone().min(p - r))
+}
+
+/// Write `p - 1 = q * 2^s` with `q` odd, and keep the invariant `r^2 == a * t (mod p)`
+/// while the order of `t` halves each round, until `t == 1`.
+fn tonelli_shanks(a: &BigInt, p: &BigInt) -> BigInt {
+    let p_minus_1 = p - 1u8;
+    let s = p_minus_1.trailing_zeros().unwrap();
+    let q = &p_minus_1 >> s;
+
+    let z = (2u32..)
+        .map(BigInt::from)
+        .find(|z| legendre(z, p) == -1)
+        .unwrap();
+    let mut m = s;
+    let mut c = modpow(&z, &q, p);
+    let mut t = modpow(a, &q, p);
+    let mut r = modpow(a, &((&q + 1u8) >> 1), p);
+    while !t.is_one() {
+        // Least i with t^(2^i) == 1, then 0 < i < m.
+        let mut i = 0;
+        let mut t_pow = t.clone();
+        while !t_pow.is_one() {
+            t_pow = (&t_pow * &t_pow).rem_euclid(p);
+            i += 1;
+        }
+        let b = modpow(&c, &(const_1() << (m - i - 1)), p);
+        m = i;
+        c = (&b * &b).rem_euclid(p);
+        t = (&t * &c).rem_euclid(p);
+        r = (&r * &b).rem_euclid(p);
+    }
+    r
+}
+
+use crate::modpow;
+use crate::prelude::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_jacobi() {
+        // Compare with Euler's criterion for primes, and multiplicativity for composites.
+        for p in [3i64, 5, 7, 11, 13, 17, 41, 97, 7919] {
+            let p = BigInt::from(p);
+            for a in -20i64..60 {
+                let a = BigInt::from(a);
+                let euler = modpow(&a, &((&p - 1) >> 1), &p);
+                let expected = if euler.is_zero() {
+                    0
+                } else if euler.is_one() {
+                    1
+                } else {
+                    -1
+                };
+                assert_eq!(legendre(&a, &p), expected, "({}/{})", a, p);
+            }
+        }
+        for a in 0i64..50 {
+            let a = BigInt::from(a);
+            let n = BigInt::from(3 * 5 * 5 * 13);
+            let expected = legendre(&a, &BigInt::from(3))
+                * legendre(&a, &BigInt::from(5))
+                * legendre(&a, &BigInt::from(5))
+                * legendre(&a, &BigInt::from(13));
+            assert_eq!(jacobi(&a, &n), expected);
+        }
+        assert_eq!(jacobi(&BigInt::from(1001), &BigInt::from(9907)), -1);
+        assert_eq!(jacobi(&BigInt::from(19), &BigInt::from(45)), 1); // not a square though
+    }
+
+    #[test]
+    fn test_modsqrt() {
+        // 3 mod 4, 5 mod 8, and 1 mod 8 (Tonelli–Shanks) primes.
+        for p in [7i64, 11, 13, 29, 17, 41, 97, 7937, 65537] {
+            let p = BigInt::from(p);
+            let mut squares = 0;
+            for a in 0i64..200 {
+                let a = BigInt::from(a);
+                match modsqrt(&a, &p) {
+                    Some(r) => {
+                        assert_eq!((&r * &r).rem_euclid(&p), a.rem_euclid(&p));
+                        assert!(r <= &p - &r || r.is_zero());
+                        squares += 1;
+                    }
+                    None => assert_eq!(legendre(&a, &p), -1),
+                }
+            }
+            assert!(squares > 0);
+        }
+
+        // Big primes: 2^127 - 1 == 3 (mod 4), and the secp256k1 order == 1 (mod 8)
+        // with 2-adicity 6, exercising Tonelli–Shanks.
+        for p in [const_mersenne12(), const_secp256k1_order()] {
+            let x = BigInt::from(1145141919810893u64);
+            let r = modsqrt(&(&x * &x), &p).unwrap();
+            assert!(r == x || r == &p - &x);
+        }
+    }
+}
@@ -0,0 +1,53 @@
+//! Quadratic sharing and reconstruction against the subproduct tree paths.
+//!
+//! Run with `cargo bench -p shamir_secret_sharing`.
+
+fn bench_field<F: FiniteField>(c: &mut Criterion, name: &str, p: &F, sizes: &[usize]) {
+    let s = p.int_to_elem(1145141919810893);
+    let mut group = c.benchmark_group(name);
+    group.sample_size(10);
+    for n in sizes.iter() {
+        let k = n / 2;
+        group.bench_with_input(BenchmarkId::new("share_secret", n), n, |b, n| {
+            b.iter(|| share_secret(&s, k, *n, p))
+        });
+        group.bench_with_input(BenchmarkId::new("share_secret_fast", n), n, |b, n| {
+            b.iter(|| share_secret_fast(&s, k, *n, p))
+        });
+
+        let shares = share_secret(&s, k, *n, p);
+        let view: Vec<&ShamirShare<F::Elem>> = shares.iter().take(k).collect();
+        group.bench_with_input(
+            BenchmarkId::new("lagrange_interpolate", n),
+            &view,
+            |b, view| b.iter(|| lagrange_interpolate(view, p)),
+        );
+        group.bench_with_input(
+            BenchmarkId::new("lagrange_interpolate_fast", n),
+            &view,
+            |b, view| b.iter(|| lagrange_interpolate_fast(view, p)),
+        );
+    }
+    group.finish();
+}
+
+fn benches(c: &mut Criterion) {
+    // Has roots of unity up to 2^57, so products go through the NTT.
+    bench_field(
+        c,
+        "ntt_prime",
+        &SmallPrimeField::new(NTT_PRIME),
+        &[256, 1024, 4096],
+    );
+    // No large roots of unity: schoolbook products inside the tree.
+    bench_field(c, "secp256k1_order", &ScalarField, &[256, 1024]);
+}
+
+criterion_group!(fast_sharing, benches);
+criterion_main!(fast_sharing);
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use shamir_secret_sharing::{
+    lagrange_interpolate, lagrange_interpolate_fast, share_secret, share_secret_fast, FiniteField,
+    ScalarField, ShamirShare, SmallPrimeField, NTT_PRIME,
+};
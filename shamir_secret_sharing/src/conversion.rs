@@ -0,0 +1,92 @@
+//! Conversion between Shamir shares and additive shares.
+//!
+//! Shamir to additive: members `ids` of a quorum scale their shares by Lagrange coefficients,
+//! `w_i = \lambda_i(0) * f(i)`, so that `f(0) = \sum_{i \in ids} w_i`. Fixed to that quorum.
+//!
+//! Additive to Shamir: each holder of `a_i` deals a fresh Shamir sharing of it,
+//! and each member sums the shares it receives into a share of `\sum_i a_i`.
+
+/// Additive share of member `share.id` within the quorum `ids`.
+pub fn shamir_to_additive<F: FiniteField>(
+    share: &ShamirShare<F::Elem>,
+    ids: &[usize],
+    p: &F,
+) -> F::Elem {
+    p.mul(&lagrange_basis_at(share.id, ids, &p.zero(), p), &share.val)
+}
+
+/// Deal the additive share `a` to members `1..=n`, `k` of which recover it.
+pub fn additive_to_shamir<F: FiniteField>(
+    a: &F::Elem,
+    k: usize,
+    n: usize,
+    p: &F,
+) -> Vec<ShamirShare<F::Elem>> {
+    share_secret(a, k, n, p)
+}
+
+/// Same as `additive_to_shamir`, drawing the coefficients from `rng`.
+pub fn additive_to_shamir_with_rng<F: FiniteField, R: CryptoRng + RngCore>(
+    a: &F::Elem,
+    k: usize,
+    n: usize,
+    p: &F,
+    rng: &mut R,
+) -> Vec<ShamirShare<F::Elem>> {
+    share_secret_with_rng(a, k, n, p, rng)
+}
+
+/// Sum the shares one member received from all dealers into its share of the sum of their secrets.
+pub fn sum_shamir_shares<F: FiniteField>(
+    shares: &[&ShamirShare<F::Elem>],
+    p: &F,
+) -> ShamirShare<F::Elem> {
+    assert!(!shares.is_empty());
+    let id = shares[0].id;
+    assert!(
+        shares.iter().all(|s| s.id == id),
+        "shares of different members"
+    );
+    ShamirShare {
+        id,
+        val: shares.iter().fold(p.zero(), |acc, s| p.add(&acc, &s.val)),
+    }
+}
+
+use crate::{lagrange_basis_at, share_secret, share_secret_with_rng, FiniteField, ShamirShare};
+use rand::{CryptoRng, RngCore};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{lagrange_interpolate, ScalarField};
+    use k256::Scalar;
+
+    #[test]
+    fn test_conversion() {
+        let s = Scalar::from(1145141919810893u64);
+        let shares = share_secret(&s, 3, 5, &ScalarField);
+
+        // Shamir to additive.
+        let ids = [1, 3, 4];
+        let additive: Vec<Scalar> = ids
+            .iter()
+            .map(|id| shamir_to_additive(&shares[id - 1], &ids, &ScalarField))
+            .collect();
+        assert_eq!(additive.iter().sum::<Scalar>(), s);
+
+        // Additive back to Shamir, with a different threshold.
+        let dealt: Vec<Vec<ShamirShare<Scalar>>> = additive
+            .iter()
+            .map(|a| additive_to_shamir(a, 2, 4, &ScalarField))
+            .collect();
+        let resummed: Vec<ShamirShare<Scalar>> = (0..4)
+            .map(|i| {
+                let received: Vec<&ShamirShare<Scalar>> = dealt.iter().map(|d| &d[i]).collect();
+                sum_shamir_shares(&received, &ScalarField)
+            })
+            .collect();
+        let view = [&resummed[3], &resummed[1]];
+        assert_eq!(lagrange_interpolate(&view, &ScalarField), s);
+    }
+}
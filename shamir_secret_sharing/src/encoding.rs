@@ -0,0 +1,572 @@
+//! Self-describing, versioned encoding of a Shamir share.
+//!
+//! Binary layout of version 1, integers in big endian:
+//! ```text
+//! "SSS" | version: u8 | secret_id: [u8; 16] | threshold: u16
+//!       | field_tag: u8 | field_param_len: u16 | field_param
+//!       | id: u16 | val_len: u32 | val
+//!       | checksum: first 4 bytes of SHA256(SHA256(everything above))
+//! ```
+//! The text forms are base58 or hex of the binary form.
+
+pub const SHARE_FORMAT_VERSION: u8 = 1;
+const MAGIC: &[u8; 3] = b"SSS";
+
+/// Identifies the split a share belongs to. Random, so it reveals nothing about the secret.
+pub type SecretId = [u8; 16];
+
+pub fn new_secret_id() -> SecretId {
+    new_secret_id_with_rng(&mut rand::thread_rng())
+}
+
+pub fn new_secret_id_with_rng<R: CryptoRng + RngCore>(rng: &mut R) -> SecretId {
+    let mut id = [0u8; 16];
+    rng.fill_bytes(&mut id);
+    id
+}
+
+/// The field a share lives in.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FieldId {
+    Prime(BigInt),
+    Secp256k1Order,
+    SmallPrime(u64),
+    /// Byte-wise sharing, see `split_bytes`.
+    Gf256,
+}
+
+impl FieldId {
+    /// Whether `id` maps to a non-zero point of the field, as `FiniteField::id_to_point` requires.
+    pub fn accepts_id(&self, id: u16) -> bool {
+        let id = id as u64;
+        id != 0
+            && match self {
+                FieldId::Prime(p) => &BigInt::from(id) < p,
+                FieldId::Secp256k1Order => true,
+                FieldId::SmallPrime(p) => id < *p,
+                FieldId::Gf256 => id <= u8::MAX as u64,
+            }
+    }
+}
+
+/// A field whose elements can be written into a [`ShareEnvelope`].
+pub trait ShareField: FiniteField {
+    fn field_id(&self) -> FieldId;
+    fn elem_to_bytes(&self, a: &Self::Elem) -> Vec<u8>;
+    /// `None` if `bytes` is not the encoding of an element.
+    fn elem_from_bytes(&self, bytes: &[u8]) -> Option<Self::Elem>;
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ShareEnvelope {
+    pub version: u8,
+    pub secret_id: SecretId,
+    pub threshold: u16,
+    pub field: FieldId,
+    pub id: u16,
+    pub val: Vec<u8>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ShareFormatError {
+    BadMagic,
+    UnsupportedVersion(u8),
+    Truncated,
+    TrailingBytes,
+    ChecksumMismatch,
+    /// Not valid base58 or hex, or an unknown field tag.
+    BadEncoding,
+    /// The value is not an element of the expected field.
+    BadValue {
+        id: u16,
+    },
+    /// The id is 0, or too large to be a distinct point of the field, e.g. above 255 in GF(256).
+    BadId {
+        id: u16,
+    },
+    /// Shares come from different splits, or disagree on threshold or field.
+    MixedSplits,
+    FieldMismatch {
+        expected: FieldId,
+        found: FieldId,
+    },
+    DuplicateIds,
+    NotEnoughShares {
+        need: usize,
+        got: usize,
+    },
+}
+
+impl ShareEnvelope {
+    pub fn new<F: ShareField>(
+        share: &ShamirShare<F::Elem>,
+        field: &F,
+        secret_id: SecretId,
+        threshold: usize,
+    ) -> Self {
+        Self::new_raw(
+            share.id,
+            field.elem_to_bytes(&share.val),
+            field.field_id(),
+            secret_id,
+            threshold,
+        )
+    }
+
+    /// Wrap a share from `split_bytes`.
+    pub fn from_byte_share(
+        share: &ShamirShare<Vec<u8>>,
+        secret_id: SecretId,
+        threshold: usize,
+    ) -> Self {
+        Self::new_raw(
+            share.id,
+            share.val.clone(),
+            FieldId::Gf256,
+            secret_id,
+            threshold,
+        )
+    }
+
+    fn new_raw(
+        id: usize,
+        val: Vec<u8>,
+        field: FieldId,
+        secret_id: SecretId,
+        threshold: usize,
+    ) -> Self {
+        Self {
+            version: SHARE_FORMAT_VERSION,
+            secret_id,
+            threshold: threshold.try_into().expect("threshold exceeds u16"),
+            field,
+            id: id.try_into().expect("id exceeds u16"),
+            val,
+        }
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buf: Vec<u8> = Vec::new();
+        buf.extend_from_slice(MAGIC);
+        buf.push(self.version);
+        buf.extend_from_slice(&self.secret_id);
+        buf.extend_from_slice(&self.threshold.to_be_bytes());
+        let (tag, param) = match &self.field {
+            FieldId::Prime(p) => (0u8, p.to_bytes_be().1),
+            FieldId::Secp256k1Order => (1u8, vec![]),
+            FieldId::SmallPrime(p) => (2u8, p.to_be_bytes().to_vec()),
+            FieldId::Gf256 => (3u8, vec![]),
+        };
+        buf.push(tag);
+        let param_len: u16 = param.len().try_into().expect("modulus too long");
+        buf.extend_from_slice(&param_len.to_be_bytes());
+        buf.extend_from_slice(&param);
+        buf.extend_from_slice(&self.id.to_be_bytes());
+        let val_len: u32 = self.val.len().try_into().expect("share too long");
+        buf.extend_from_slice(&val_len.to_be_bytes());
+        buf.extend_from_slice(&self.val);
+        let checksum = checksum(&buf);
+        buf.extend_from_slice(&checksum);
+        buf
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ShareFormatError> {
+        use ShareFormatError::*;
+
+        if bytes.len() < MAGIC.len() + 1 + 4 {
+            return Err(Truncated);
+        }
+        if &bytes[..3] != MAGIC {
+            return Err(BadMagic);
+        }
+        if bytes[3] != SHARE_FORMAT_VERSION {
+            return Err(UnsupportedVersion(bytes[3]));
+        }
+        let (body, check) = bytes.split_at(bytes.len() - 4);
+        if checksum(body) != check {
+            return Err(ChecksumMismatch);
+        }
+
+        let mut reader = Reader(&body[4..]);
+        let secret_id: SecretId = reader.take(16)?.try_into().unwrap();
+        let threshold = u16::from_be_bytes(reader.take(2)?.try_into().unwrap());
+        let tag = reader.take(1)?[0];
+        let param_len = u16::from_be_bytes(reader.take(2)?.try_into().unwrap());
+        let param = reader.take(param_len as usize)?;
+        let field = match (tag, param.len()) {
+            (0, _) => FieldId::Prime(BigInt::from_bytes_be(num_bigint::Sign::Plus, param)),
+            (1, 0) => FieldId::Secp256k1Order,
+            (2, 8) => FieldId::SmallPrime(u64::from_be_bytes(param.try_into().unwrap())),
+            (3, 0) => FieldId::Gf256,
+            _ => return Err(BadEncoding),
+        };
+        let id = u16::from_be_bytes(reader.take(2)?.try_into().unwrap());
+        let val_len = u32::from_be_bytes(reader.take(4)?.try_into().unwrap());
+        let val = reader.take(val_len as usize)?.to_vec();
+        if !reader.0.is_empty() {
+            return Err(TrailingBytes);
+        }
+        if !field.accepts_id(id) {
+            return Err(BadId { id });
+        }
+        Ok(Self {
+            version: SHARE_FORMAT_VERSION,
+            secret_id,
+            threshold,
+            field,
+            id,
+            val,
+        })
+    }
+
+    pub fn to_base58(&self) -> String {
+        bs58::encode(self.to_bytes()).into_string()
+    }
+
+    pub fn from_base58(text: &str) -> Result<Self, ShareFormatError> {
+        let bytes = bs58::decode(text.trim())
+            .into_vec()
+            .map_err(|_| ShareFormatError::BadEncoding)?;
+        Self::from_bytes(&bytes)
+    }
+
+    pub fn to_hex(&self) -> String {
+        hex::encode(self.to_bytes())
+    }
+
+    pub fn from_hex(text: &str) -> Result<Self, ShareFormatError> {
+        let bytes = hex::decode(text.trim()).map_err(|_| ShareFormatError::BadEncoding)?;
+        Self::from_bytes(&bytes)
+    }
+}
+
+/// Check that the envelopes can be combined: same split, distinct ids, and at least `threshold` of them.
+pub fn check_same_split(envs: &[ShareEnvelope]) -> Result<(), ShareFormatError> {
+    let first = envs
+        .first()
+        .ok_or(ShareFormatError::NotEnoughShares { need: 1, got: 0 })?;
+    let same = envs.iter().all(|e| {
+        e.secret_id == first.secret_id && e.threshold == first.threshold && e.field == first.field
+    });
+    if !same {
+        return Err(ShareFormatError::MixedSplits);
+    }
+    if let Some(e) = envs.iter().find(|e| !e.field.accepts_id(e.id)) {
+        return Err(ShareFormatError::BadId { id: e.id });
+    }
+    let ids: HashSet<u16> = envs.iter().map(|e| e.id).collect();
+    if ids.len() != envs.len() {
+        return Err(ShareFormatError::DuplicateIds);
+    }
+    if envs.len() < first.threshold as usize {
+        return Err(ShareFormatError::NotEnoughShares {
+            need: first.threshold as usize,
+            got: envs.len(),
+        });
+    }
+    Ok(())
+}
+
+/// Unwrap envelopes of one split into shares over `field`, ready for `lagrange_interpolate`.
+pub fn open_shares<F: ShareField>(
+    envs: &[ShareEnvelope],
+    field: &F,
+) -> Result<Vec<ShamirShare<F::Elem>>, ShareFormatError> {
+    check_same_split(envs)?;
+    let expected = field.field_id();
+    if envs[0].field != expected {
+        return Err(ShareFormatError::FieldMismatch {
+            expected,
+            found: envs[0].field.clone(),
+        });
+    }
+    envs.iter()
+        .map(|e| match field.elem_from_bytes(&e.val) {
+            Some(val) => Ok(ShamirShare {
+                id: e.id as usize,
+                val,
+            }),
+            None => Err(ShareFormatError::BadValue { id: e.id }),
+        })
+        .collect()
+}
+
+/// Unwrap envelopes of one split into shares for `combine_bytes`.
+pub fn open_byte_shares(
+    envs: &[ShareEnvelope],
+) -> Result<Vec<ShamirShare<Vec<u8>>>, ShareFormatError> {
+    check_same_split(envs)?;
+    if envs[0].field != FieldId::Gf256 {
+        return Err(ShareFormatError::FieldMismatch {
+            expected: FieldId::Gf256,
+            found: envs[0].field.clone(),
+        });
+    }
+    Ok(envs
+        .iter()
+        .map(|e| ShamirShare {
+            id: e.id as usize,
+            val: e.val.clone(),
+        })
+        .collect())
+}
+
+fn checksum(bytes: &[u8]) -> [u8; 4] {
+    let hash = Sha256::digest(Sha256::digest(bytes));
+    hash[..4].try_into().unwrap()
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], ShareFormatError> {
+        if self.0.len() < len {
+            return Err(ShareFormatError::Truncated);
+        }
+        let (head, tail) = self.0.split_at(len);
+        self.0 = tail;
+        Ok(head)
+    }
+}
+
+impl ShareField for BigInt {
+    fn field_id(&self) -> FieldId {
+        FieldId::Prime(self.clone())
+    }
+
+    fn elem_to_bytes(&self, a: &BigInt) -> Vec<u8> {
+        a.to_bytes_be().1
+    }
+
+    fn elem_from_bytes(&self, bytes: &[u8]) -> Option<BigInt> {
+        let a = BigInt::from_bytes_be(num_bigint::Sign::Plus, bytes);
+        (&a < self).then_some(a)
+    }
+}
+
+impl ShareField for ScalarField {
+    fn field_id(&self) -> FieldId {
+        FieldId::Secp256k1Order
+    }
+
+    fn elem_to_bytes(&self, a: &Scalar) -> Vec<u8> {
+        a.to_bytes().to_vec()
+    }
+
+    fn elem_from_bytes(&self, bytes: &[u8]) -> Option<Scalar> {
+        let bytes: [u8; 32] = bytes.try_into().ok()?;
+        Scalar::from_repr(bytes.into()).into()
+    }
+}
+
+impl ShareField for SmallPrimeField {
+    fn field_id(&self) -> FieldId {
+        FieldId::SmallPrime(self.p())
+    }
+
+    fn elem_to_bytes(&self, a: &u64) -> Vec<u8> {
+        a.to_be_bytes().to_vec()
+    }
+
+    fn elem_from_bytes(&self, bytes: &[u8]) -> Option<u64> {
+        let a = u64::from_be_bytes(bytes.try_into().ok()?);
+        (a < self.p()).then_some(a)
+    }
+}
+
+impl ShareField for Gf256 {
+    fn field_id(&self) -> FieldId {
+        FieldId::Gf256
+    }
+
+    fn elem_to_bytes(&self, a: &u8) -> Vec<u8> {
+        vec![*a]
+    }
+
+    fn elem_from_bytes(&self, bytes: &[u8]) -> Option<u8> {
+        match bytes {
+            [a] => Some(*a),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for ShareFormatError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use ShareFormatError::*;
+        match self {
+            BadMagic => write!(f, "not a share"),
+            UnsupportedVersion(v) => write!(f, "unsupported share format version {}", v),
+            Truncated => write!(f, "share is truncated"),
+            TrailingBytes => write!(f, "trailing bytes after share"),
+            ChecksumMismatch => write!(f, "share checksum mismatch"),
+            BadEncoding => write!(f, "malformed share encoding"),
+            BadValue { id } => write!(f, "value of share {} is not a field element", id),
+            BadId { id } => write!(f, "share id {} is not a valid point of the field", id),
+            MixedSplits => write!(f, "shares come from different splits"),
+            FieldMismatch { expected, found } => {
+                write!(f, "expected field {:?}, found {:?}", expected, found)
+            }
+            DuplicateIds => write!(f, "duplicate share ids"),
+            NotEnoughShares { need, got } => write!(f, "need {} shares, got {}", need, got),
+        }
+    }
+}
+
+impl std::error::Error for ShareFormatError {}
+
+use std::collections::HashSet;
+
+use crate::{FiniteField, Gf256, ScalarField, ShamirShare, SmallPrimeField};
+use k256::{elliptic_curve::PrimeField, Scalar};
+use num_bigint::BigInt;
+use rand::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{combine_bytes, lagrange_interpolate, share_secret, split_bytes};
+    use modulo_arithmetic::prelude::const_mersenne12;
+
+    #[test]
+    fn test_roundtrip() {
+        let p = const_mersenne12();
+        let s = BigInt::from(1145141919810893i64);
+        let secret_id = new_secret_id();
+        let shares = share_secret(&s, 3, 5, &p);
+        let texts: Vec<String> = shares
+            .iter()
+            .map(|share| ShareEnvelope::new(share, &p, secret_id, 3).to_base58())
+            .collect();
+
+        let envs: Vec<ShareEnvelope> = [&texts[0], &texts[3], &texts[4]]
+            .iter()
+            .map(|t| ShareEnvelope::from_base58(t).unwrap())
+            .collect();
+        let opened = open_shares(&envs, &p).unwrap();
+        let view: Vec<&ShamirShare> = opened.iter().collect();
+        assert_eq!(lagrange_interpolate(&view, &p), s);
+
+        // Hex and binary forms.
+        let env = ShareEnvelope::new(&shares[1], &p, secret_id, 3);
+        assert_eq!(ShareEnvelope::from_hex(&env.to_hex()), Ok(env.clone()));
+        assert_eq!(ShareEnvelope::from_bytes(&env.to_bytes()), Ok(env));
+
+        // Wrong field.
+        assert!(matches!(
+            open_shares(&envs, &ScalarField),
+            Err(ShareFormatError::FieldMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_roundtrip_bytes() {
+        let secret = b"correct horse battery staple";
+        let secret_id = new_secret_id();
+        let shares = split_bytes(secret, 2, 3);
+        let envs: Vec<ShareEnvelope> = shares[1..]
+            .iter()
+            .map(|share| ShareEnvelope::from_byte_share(share, secret_id, 2))
+            .map(|env| ShareEnvelope::from_hex(&env.to_hex()).unwrap())
+            .collect();
+        let opened = open_byte_shares(&envs).unwrap();
+        let view: Vec<&ShamirShare<Vec<u8>>> = opened.iter().collect();
+        assert_eq!(combine_bytes(&view), secret);
+    }
+
+    #[test]
+    fn test_reject() {
+        let s = Scalar::from(42u64);
+        let split_a = share_secret(&s, 2, 3, &ScalarField);
+        let split_b = share_secret(&s, 2, 3, &ScalarField);
+        let (id_a, id_b) = (new_secret_id(), new_secret_id());
+        let env_a = ShareEnvelope::new(&split_a[0], &ScalarField, id_a, 2);
+        let env_b = ShareEnvelope::new(&split_b[1], &ScalarField, id_b, 2);
+
+        // Shares from different splits.
+        let res = open_shares(&[env_a.clone(), env_b], &ScalarField);
+        assert_eq!(res, Err(ShareFormatError::MixedSplits));
+
+        // Too few and duplicate shares.
+        let res = open_shares(std::slice::from_ref(&env_a), &ScalarField);
+        assert_eq!(
+            res,
+            Err(ShareFormatError::NotEnoughShares { need: 2, got: 1 })
+        );
+        let res = open_shares(&[env_a.clone(), env_a.clone()], &ScalarField);
+        assert_eq!(res, Err(ShareFormatError::DuplicateIds));
+
+        // Corruption.
+        let mut bytes = env_a.to_bytes();
+        bytes[30] ^= 1;
+        assert_eq!(
+            ShareEnvelope::from_bytes(&bytes),
+            Err(ShareFormatError::ChecksumMismatch)
+        );
+        let bytes = env_a.to_bytes();
+        assert_eq!(
+            ShareEnvelope::from_bytes(&bytes[..bytes.len() - 1]),
+            Err(ShareFormatError::ChecksumMismatch)
+        );
+        let mut bytes = env_a.to_bytes();
+        bytes[3] = 2;
+        assert_eq!(
+            ShareEnvelope::from_bytes(&bytes),
+            Err(ShareFormatError::UnsupportedVersion(2))
+        );
+        assert_eq!(
+            ShareEnvelope::from_hex("zz"),
+            Err(ShareFormatError::BadEncoding)
+        );
+    }
+
+    #[test]
+    fn test_reject_id() {
+        let secret_id = new_secret_id();
+        let share = ShamirShare {
+            id: 0,
+            val: Scalar::from(42u64),
+        };
+        let env = ShareEnvelope::new(&share, &ScalarField, secret_id, 1);
+        assert_eq!(
+            ShareEnvelope::from_bytes(&env.to_bytes()),
+            Err(ShareFormatError::BadId { id: 0 })
+        );
+        assert_eq!(
+            open_shares(&[env], &ScalarField),
+            Err(ShareFormatError::BadId { id: 0 })
+        );
+
+        let p = SmallPrimeField::new(7);
+        let share = ShamirShare { id: 7, val: 3 };
+        let env = ShareEnvelope::new(&share, &p, secret_id, 1);
+        assert_eq!(
+            open_shares(&[env], &p),
+            Err(ShareFormatError::BadId { id: 7 })
+        );
+
+        for id in [0, 256] {
+            let share = ShamirShare {
+                id,
+                val: vec![1, 2, 3],
+            };
+            let env = ShareEnvelope::from_byte_share(&share, secret_id, 1);
+            assert_eq!(
+                ShareEnvelope::from_hex(&env.to_hex()),
+                Err(ShareFormatError::BadId { id: id as u16 })
+            );
+            assert_eq!(
+                open_byte_shares(&[env]),
+                Err(ShareFormatError::BadId { id: id as u16 })
+            );
+        }
+        let share = ShamirShare {
+            id: 255,
+            val: vec![1, 2, 3],
+        };
+        let env = ShareEnvelope::from_byte_share(&share, secret_id, 1);
+        assert!(open_byte_shares(&[env]).is_ok());
+    }
+}
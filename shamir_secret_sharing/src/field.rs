@@ -0,0 +1,389 @@
+/// A finite field that secrets are shared over.
+///
+/// The implementor describes the field (e.g. its modulus),
+/// while `Elem` is the type of its elements.
+/// So `share_secret(&s, k, n, &p)` works alike for
+/// * `p: BigInt`, i.e. integers modulo a (big) prime `p`,
+/// * `p: ScalarField`, i.e. `k256::Scalar`, integers modulo the secp256k1 order,
+/// * `p: SmallPrimeField`, i.e. `u64` modulo a prime `p < 2^63`, and
+/// * `p: Gf256`, i.e. bytes as GF(2^8), of characteristic 2.
+///
+/// Share ids and integers are mapped differently: `id_to_point` keeps ids apart,
+/// while `int_to_elem` is the ring embedding of the integers, e.g. `2 == 0` in GF(2^8).
+pub trait FiniteField {
+    type Elem: Clone + Debug + PartialEq;
+
+    fn zero(&self) -> Self::Elem;
+    fn one(&self) -> Self::Elem;
+
+    /// The evaluation point of share `id`, e.g. `f(id_to_point(id))` is the share.
+    /// Injective: panics if `id` is out of range, so that distinct ids never share a point.
+    fn id_to_point(&self, id: usize) -> Self::Elem;
+
+    /// The integer `n`, i.e. `one()` added `n` times, reduced by the characteristic.
+    /// Used for integer factors like those of derivatives, never for share ids.
+    fn int_to_elem(&self, n: u64) -> Self::Elem;
+
+    fn add(&self, a: &Self::Elem, b: &Self::Elem) -> Self::Elem;
+    fn sub(&self, a: &Self::Elem, b: &Self::Elem) -> Self::Elem;
+    fn mul(&self, a: &Self::Elem, b: &Self::Elem) -> Self::Elem;
+
+    /// Multiplicative inverse. Panics if `a` is zero.
+    fn inv(&self, a: &Self::Elem) -> Self::Elem;
+
+    /// Uniformly random element, possibly zero, e.g. for polynomial coefficients and masks.
+    fn random_uniform<R: CryptoRng + RngCore + ?Sized>(&self, rng: &mut R) -> Self::Elem;
+
+    /// Uniformly random non-zero element, where zero would be degenerate, e.g. an inverse.
+    fn random_nonzero<R: CryptoRng + RngCore + ?Sized>(&self, rng: &mut R) -> Self::Elem {
+        loop {
+            let x = self.random_uniform(rng);
+            if !self.is_zero(&x) {
+                return x;
+            }
+        }
+    }
+
+    fn neg(&self, a: &Self::Elem) -> Self::Elem {
+        self.sub(&self.zero(), a)
+    }
+
+    fn div(&self, a: &Self::Elem, b: &Self::Elem) -> Self::Elem {
+        self.mul(a, &self.inv(b))
+    }
+
+    fn is_zero(&self, a: &Self::Elem) -> bool {
+        *a == self.zero()
+    }
+
+    /// Inverses of all `xs` with a single `inv`, by Montgomery's trick.
+    /// Panics if any of them is zero.
+    fn batch_inv(&self, xs: &[Self::Elem]) -> Vec<Self::Elem> {
+        let mut prefix: Vec<Self::Elem> = Vec::with_capacity(xs.len());
+        let mut acc = self.one();
+        for x in xs.iter() {
+            acc = self.mul(&acc, x);
+            prefix.push(acc.clone());
+        }
+        let mut acc_inv = self.inv(&acc);
+        let mut invs = vec![self.zero(); xs.len()];
+        for i in (0..xs.len()).rev() {
+            invs[i] = match i {
+                0 => acc_inv.clone(),
+                _ => self.mul(&prefix[i - 1], &acc_inv),
+            };
+            acc_inv = self.mul(&acc_inv, &xs[i]);
+        }
+        invs
+    }
+
+    /// A primitive `n`-th root of unity for `n` a power of 2, if the field has one.
+    /// Fields that have them get the NTT paths of `ntt` and `Polynomial`.
+    fn root_of_unity(&self, _n: usize) -> Option<Self::Elem> {
+        None
+    }
+}
+
+/// Integers modulo the prime `self`.
+impl FiniteField for BigInt {
+    type Elem = BigInt;
+
+    fn zero(&self) -> BigInt {
+        const_0()
+    }
+
+    fn one(&self) -> BigInt {
+        const_1()
+    }
+
+    fn id_to_point(&self, id: usize) -> BigInt {
+        let x = BigInt::from(id);
+        assert!(
+            &x < self,
+            "{} is out of range for the field of order {}",
+            x,
+            self
+        );
+        x
+    }
+
+    fn int_to_elem(&self, n: u64) -> BigInt {
+        BigInt::from(n).rem_euclid(self)
+    }
+
+    fn add(&self, a: &BigInt, b: &BigInt) -> BigInt {
+        (a + b).rem_euclid(self)
+    }
+
+    fn sub(&self, a: &BigInt, b: &BigInt) -> BigInt {
+        (a - b).rem_euclid(self)
+    }
+
+    fn mul(&self, a: &BigInt, b: &BigInt) -> BigInt {
+        (a * b).rem_euclid(self)
+    }
+
+    fn inv(&self, a: &BigInt) -> BigInt {
+        modinv(a, self)
+    }
+
+    fn random_uniform<R: CryptoRng + RngCore + ?Sized>(&self, rng: &mut R) -> BigInt {
+        rng.gen_bigint_range(&const_0(), self)
+    }
+
+    fn div(&self, a: &BigInt, b: &BigInt) -> BigInt {
+        moddiv(a, b, self)
+    }
+
+    fn batch_inv(&self, xs: &[BigInt]) -> Vec<BigInt> {
+        batch_modinv(xs, self)
+    }
+
+    fn root_of_unity(&self, n: usize) -> Option<BigInt> {
+        let p_minus_1 = self - const_1();
+        if !n.is_power_of_two() || &p_minus_1 % n != const_0() {
+            return None;
+        }
+        let exp = &p_minus_1 / n;
+        // Order of `w` divides `n`, and is exactly `n` iff `w^(n/2) != 1`.
+        (2u64..).map(BigInt::from).take(64).find_map(|g| {
+            let w = g.modpow(&exp, self);
+            let half = w.modpow(&BigInt::from(n / 2), self);
+            (n == 1 || half != const_1()).then_some(w)
+        })
+    }
+}
+
+/// Integers modulo the secp256k1 order, represented by `k256::Scalar`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ScalarField;
+
+impl FiniteField for ScalarField {
+    type Elem = Scalar;
+
+    fn zero(&self) -> Scalar {
+        Scalar::ZERO
+    }
+
+    fn one(&self) -> Scalar {
+        Scalar::ONE
+    }
+
+    fn id_to_point(&self, id: usize) -> Scalar {
+        Scalar::from(id as u64)
+    }
+
+    fn int_to_elem(&self, n: u64) -> Scalar {
+        Scalar::from(n)
+    }
+
+    fn add(&self, a: &Scalar, b: &Scalar) -> Scalar {
+        a + b
+    }
+
+    fn sub(&self, a: &Scalar, b: &Scalar) -> Scalar {
+        a - b
+    }
+
+    fn mul(&self, a: &Scalar, b: &Scalar) -> Scalar {
+        a * b
+    }
+
+    fn inv(&self, a: &Scalar) -> Scalar {
+        Option::from(a.invert()).expect("a has no multiplicative inverse")
+    }
+
+    fn random_uniform<R: CryptoRng + RngCore + ?Sized>(&self, rng: &mut R) -> Scalar {
+        Scalar::random(&mut *rng)
+    }
+
+    fn neg(&self, a: &Scalar) -> Scalar {
+        -a
+    }
+
+    /// The secp256k1 order has only `2^6` as 2-adic part, so `n <= 64`.
+    fn root_of_unity(&self, n: usize) -> Option<Scalar> {
+        let s = Scalar::S as usize;
+        if !n.is_power_of_two() || n > 1 << s {
+            return None;
+        }
+        let mut w = Scalar::ROOT_OF_UNITY;
+        for _ in n.trailing_zeros() as usize..s {
+            w = w.square();
+        }
+        Some(w)
+    }
+}
+
+/// Integers modulo a prime `p < 2^63`, represented by `u64`.
+/// Arguments of `add`, `sub` and `mul` may be unreduced, results are always reduced.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SmallPrimeField(u64);
+
+impl SmallPrimeField {
+    /// Panics unless `p` is a prime below `2^63`,
+    /// as `inv` by Fermat's little theorem is wrong for composite `p`.
+    pub fn new(p: u64) -> Self {
+        assert!(p > 1 && p < (1 << 63), "p must be in (1, 2^63)");
+        assert!(is_prime(&BigInt::from(p)), "p = {} is not prime", p);
+        Self(p)
+    }
+
+    pub fn p(&self) -> u64 {
+        self.0
+    }
+}
+
+impl FiniteField for SmallPrimeField {
+    type Elem = u64;
+
+    fn zero(&self) -> u64 {
+        0
+    }
+
+    fn one(&self) -> u64 {
+        1
+    }
+
+    fn id_to_point(&self, id: usize) -> u64 {
+        let x = id as u64;
+        assert!(
+            x < self.0,
+            "{} is out of range for the field of order {}",
+            x,
+            self.0
+        );
+        x
+    }
+
+    fn int_to_elem(&self, n: u64) -> u64 {
+        n % self.0
+    }
+
+    fn add(&self, a: &u64, b: &u64) -> u64 {
+        (a % self.0 + b % self.0) % self.0
+    }
+
+    fn sub(&self, a: &u64, b: &u64) -> u64 {
+        (a % self.0 + self.0 - b % self.0) % self.0
+    }
+
+    fn mul(&self, a: &u64, b: &u64) -> u64 {
+        ((*a as u128 * *b as u128) % self.0 as u128) as u64
+    }
+
+    /// Fermat's little theorem, `a^(p-2) == a^(-1) (mod p)`.
+    fn inv(&self, a: &u64) -> u64 {
+        assert!(
+            !a.is_multiple_of(self.0),
+            "a has no multiplicative inverse mod p"
+        );
+        self.pow(*a, self.0 - 2)
+    }
+
+    fn random_uniform<R: CryptoRng + RngCore + ?Sized>(&self, rng: &mut R) -> u64 {
+        use rand::Rng;
+        rng.gen_range(0..self.0)
+    }
+
+    fn root_of_unity(&self, n: usize) -> Option<u64> {
+        let n = n as u64;
+        if !n.is_power_of_two() || !(self.0 - 1).is_multiple_of(n) {
+            return None;
+        }
+        // Order of `w` divides `n`, and is exactly `n` iff `w^(n/2) != 1`.
+        (2..self.0.min(66)).find_map(|g| {
+            let w = self.pow(g, (self.0 - 1) / n);
+            (n == 1 || self.pow(w, n / 2) != 1).then_some(w)
+        })
+    }
+}
+
+impl SmallPrimeField {
+    fn pow(&self, base: u64, mut exp: u64) -> u64 {
+        let mut base = base % self.0;
+        let mut y = 1;
+        while exp > 0 {
+            if exp & 1 == 1 {
+                y = self.mul(&y, &base);
+            }
+            base = self.mul(&base, &base);
+            exp >>= 1;
+        }
+        y
+    }
+}
+
+use std::fmt::Debug;
+
+use k256::{
+    elliptic_curve::{ff::PrimeField, Field},
+    Scalar,
+};
+use modulo_arithmetic::prelude::*;
+use modulo_arithmetic::{batch_modinv, is_prime, moddiv, modinv};
+use num_bigint::{BigInt, RandBigInt};
+use num_traits::Euclid;
+use rand::{CryptoRng, RngCore};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Gf256;
+
+    fn check_field<F: FiniteField>(field: &F) {
+        let mut rng = rand::thread_rng();
+        let a = field.random_nonzero(&mut rng);
+        let b = field.random_nonzero(&mut rng);
+        assert_eq!(field.mul(&a, &field.inv(&a)), field.one());
+        assert_eq!(field.add(&a, &field.neg(&a)), field.zero());
+        assert_eq!(field.mul(&field.div(&a, &b), &b), a);
+        assert_eq!(field.sub(&field.add(&a, &b), &b), a);
+        assert!(field.is_zero(&field.sub(&a, &a)));
+        let xs: Vec<F::Elem> = (0..5).map(|_| field.random_nonzero(&mut rng)).collect();
+        let invs: Vec<F::Elem> = xs.iter().map(|x| field.inv(x)).collect();
+        assert_eq!(field.batch_inv(&xs), invs);
+        assert_eq!(
+            field.add(&field.int_to_elem(114), &field.int_to_elem(514)),
+            field.int_to_elem(628)
+        );
+        assert_eq!(
+            (0..7).fold(field.zero(), |acc, _| field.add(&acc, &field.one())),
+            field.int_to_elem(7)
+        );
+    }
+
+    #[test]
+    fn test_fields() {
+        check_field(&const_mersenne12());
+        check_field(&const_secp256k1_order());
+        check_field(&ScalarField);
+        check_field(&SmallPrimeField::new(7919));
+        check_field(&SmallPrimeField::new((1 << 61) - 1));
+        check_field(&SmallPrimeField::new(7));
+        check_field(&Gf256);
+    }
+
+    #[test]
+    fn test_small_prime_field() {
+        let p = SmallPrimeField::new((1 << 61) - 1);
+        let big = u64::MAX - 1;
+        assert_eq!(
+            p.add(&big, &big),
+            ((2 * big as u128) % p.p() as u128) as u64
+        );
+        assert_eq!(p.sub(&0, &big), p.neg(&(big % p.p())));
+    }
+
+    #[test]
+    #[should_panic(expected = "is not prime")]
+    fn test_small_prime_field_composite() {
+        SmallPrimeField::new(7917);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of range")]
+    fn test_id_to_point_out_of_range() {
+        SmallPrimeField::new(7919).id_to_point(7919);
+    }
+}
@@ -0,0 +1,226 @@
+/// GF(2^8) with the AES reduction polynomial `x^8 + x^4 + x^3 + x + 1`.
+/// Addition is XOR, so every element is its own negation.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Gf256;
+
+impl FiniteField for Gf256 {
+    type Elem = u8;
+
+    fn zero(&self) -> u8 {
+        0
+    }
+
+    fn one(&self) -> u8 {
+        1
+    }
+
+    /// The byte `id`, so ids must be in `1..=255`.
+    fn id_to_point(&self, id: usize) -> u8 {
+        u8::try_from(id).unwrap_or_else(|_| panic!("{} is out of range for GF(256)", id))
+    }
+
+    /// The characteristic is 2, so `n` is `1` if odd and `0` if even.
+    fn int_to_elem(&self, n: u64) -> u8 {
+        (n & 1) as u8
+    }
+
+    fn add(&self, a: &u8, b: &u8) -> u8 {
+        a ^ b
+    }
+
+    fn sub(&self, a: &u8, b: &u8) -> u8 {
+        a ^ b
+    }
+
+    /// Russian peasant multiplication, reducing by `0x11b` on overflow.
+    fn mul(&self, a: &u8, b: &u8) -> u8 {
+        let (mut a, mut b) = (*a, *b);
+        let mut y: u8 = 0;
+        while b != 0 {
+            if b & 1 == 1 {
+                y ^= a;
+            }
+            let carry = a & 0x80;
+            a <<= 1;
+            if carry != 0 {
+                a ^= 0x1b;
+            }
+            b >>= 1;
+        }
+        y
+    }
+
+    /// `a^254 == a^(-1)`, since the multiplicative group has order 255.
+    fn inv(&self, a: &u8) -> u8 {
+        assert!(*a != 0, "a has no multiplicative inverse in GF(256)");
+        let mut base = *a;
+        let mut exp: u8 = 254;
+        let mut y: u8 = 1;
+        while exp > 0 {
+            if exp & 1 == 1 {
+                y = self.mul(&y, &base);
+            }
+            base = self.mul(&base, &base);
+            exp >>= 1;
+        }
+        y
+    }
+
+    fn random_uniform<R: CryptoRng + RngCore + ?Sized>(&self, rng: &mut R) -> u8 {
+        (rng.next_u32() & 0xff) as u8
+    }
+
+    fn neg(&self, a: &u8) -> u8 {
+        *a
+    }
+}
+
+/// Split a byte string of any length into `n` shares, `k` of which recover it.
+/// Each byte is shared independently over GF(256), so every share is as long as the secret.
+///
+/// Ids are `1..=n`, thus `n <= 255`.
+pub fn split_bytes(
+    secret: &[u8],
+    k: usize, // (quorum) Need >=k shares to reconstruct the secret.
+    n: usize, // Total number of shares.
+) -> Vec<ShamirShare<Vec<u8>>> {
+    split_bytes_with_rng(secret, k, n, &mut rand::thread_rng())
+}
+
+/// Same as `split_bytes`, drawing the coefficients from `rng`.
+pub fn split_bytes_with_rng<R: CryptoRng + RngCore>(
+    secret: &[u8],
+    k: usize,
+    n: usize,
+    rng: &mut R,
+) -> Vec<ShamirShare<Vec<u8>>> {
+    assert!(1 <= k && k <= n);
+    assert!(n <= 255, "GF(256) has only 255 non-zero ids");
+
+    let mut shares: Vec<ShamirShare<Vec<u8>>> = (1..=n)
+        .map(|id| ShamirShare {
+            id,
+            val: Vec::with_capacity(secret.len()),
+        })
+        .collect();
+    let mut coefs = vec![0u8; k];
+    for byte in secret.iter() {
+        coefs[0] = *byte;
+        rng.fill_bytes(&mut coefs[1..]);
+        for share in shares.iter_mut() {
+            let x = Gf256.id_to_point(share.id);
+            share.val.push(eval_polynomial(&coefs, &x, &Gf256));
+        }
+    }
+    shares
+}
+
+/// Join `>= k` shares from `split_bytes` back into the secret.
+pub fn combine_bytes(shares: &[&ShamirShare<Vec<u8>>]) -> Vec<u8> {
+    assert!(!shares.is_empty());
+    let len = shares[0].val.len();
+    assert!(
+        shares.iter().all(|s| s.val.len() == len),
+        "shares differ in length"
+    );
+    '_check_uniqueness: {
+        let ids: HashSet<usize> = shares.iter().map(|s| s.id).collect();
+        assert_eq!(shares.len(), ids.len());
+        assert!(ids.iter().all(|id| (1..=255).contains(id)));
+    }
+
+    // Lagrange coefficients depend on the ids only, so compute them once for all bytes.
+    let p = Gf256;
+    let lambdas: Vec<u8> = shares
+        .iter()
+        .map(|share_i| {
+            let x_i = p.id_to_point(share_i.id);
+            let mut λ_i = p.one();
+            for share_j in shares.iter() {
+                if share_j.id == share_i.id {
+                    continue;
+                }
+                let x_j = p.id_to_point(share_j.id);
+                λ_i = p.mul(&λ_i, &p.div(&x_j, &p.sub(&x_j, &x_i)));
+            }
+            λ_i
+        })
+        .collect();
+
+    (0..len)
+        .map(|pos| {
+            shares
+                .iter()
+                .zip(lambdas.iter())
+                .fold(0u8, |acc, (share, λ)| acc ^ p.mul(&share.val[pos], λ))
+        })
+        .collect()
+}
+
+use std::collections::HashSet;
+
+use crate::{eval_polynomial, FiniteField, ShamirShare};
+use rand::{CryptoRng, RngCore};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gf256_arith() {
+        // FIPS-197, section 4.2.
+        assert_eq!(Gf256.mul(&0x57, &0x83), 0xc1);
+        assert_eq!(Gf256.mul(&0x57, &0x13), 0xfe);
+        assert_eq!(Gf256.inv(&0x53), 0xca);
+        for a in 1..=255u8 {
+            assert_eq!(Gf256.mul(&a, &Gf256.inv(&a)), 1);
+        }
+    }
+
+    #[test]
+    fn test_split_combine_bytes() {
+        let mut secret = [0u8; 64];
+        rand::thread_rng().fill_bytes(&mut secret);
+        let shares = split_bytes(&secret, 3, 5);
+        assert!(shares.iter().all(|s| s.val.len() == secret.len()));
+
+        let view = [&shares[0], &shares[1], &shares[2]];
+        assert_eq!(combine_bytes(&view), secret);
+        let view = [&shares[4], &shares[2], &shares[0]];
+        assert_eq!(combine_bytes(&view), secret);
+        let view = [&shares[0], &shares[1], &shares[2], &shares[3], &shares[4]];
+        assert_eq!(combine_bytes(&view), secret);
+
+        let shares = split_bytes(b"", 2, 3);
+        assert_eq!(combine_bytes(&[&shares[0], &shares[1]]), b"");
+    }
+
+    #[test]
+    fn test_split_bytes_seeded() {
+        use rand::{rngs::StdRng, SeedableRng};
+        let secret = b"attack at dawn";
+        let shares1 = split_bytes_with_rng(secret, 3, 5, &mut StdRng::seed_from_u64(42));
+        let shares2 = split_bytes_with_rng(secret, 3, 5, &mut StdRng::seed_from_u64(42));
+        assert_eq!(shares1, shares2);
+        let shares3 = split_bytes_with_rng(secret, 3, 5, &mut StdRng::seed_from_u64(43));
+        assert_ne!(shares1, shares3);
+        assert_eq!(
+            combine_bytes(&[&shares3[0], &shares3[2], &shares3[4]]),
+            secret
+        );
+    }
+
+    #[test]
+    fn test_share_secret_gf256() {
+        let shares = crate::share_secret(&0x42, 3, 255, &Gf256);
+        let view = [&shares[254], &shares[0], &shares[100]];
+        assert_eq!(crate::lagrange_interpolate(&view, &Gf256), 0x42);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of range for GF(256)")]
+    fn test_share_secret_gf256_too_many() {
+        // Share 256 would be evaluated at `x == 0`, i.e. be the secret itself.
+        crate::share_secret(&0x42, 2, 256, &Gf256);
+    }
+}
@@ -0,0 +1,222 @@
+//! Hierarchical threshold sharing (Tassa), reconstructed by Birkhoff interpolation.
+//!
+//! Members are split into levels `0..=m` with thresholds `0 < k_0 < k_1 < ... < k_m = k`.
+//! A member `id` at level `i` gets the derivative `f^{(k_{i-1})}(id)` of a polynomial `f`
+//! of degree `k-1`, where `k_{-1} = 0`; level 0 therefore holds ordinary Shamir shares.
+//!
+//! A set of members can reconstruct `f(0)` iff, for every level `i`,
+//! it contains at least `k_i` members of levels `0..=i`,
+//! and the resulting Birkhoff interpolation problem is well-posed.
+//! The former rules out e.g. a quorum made only of lower-ranked members;
+//! the latter holds for almost all ids in a large field, and is checked directly.
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HierarchicalShare<T = BigInt> {
+    /// Unique and Non-Zero.
+    pub id: usize,
+    /// 0 is the most senior level.
+    pub level: usize,
+    /// `f^{(k_{level-1})}(id)`
+    pub val: T,
+}
+
+/// Order of the derivative held by members at `level`.
+pub fn derivative_order(level: usize, thresholds: &[usize]) -> usize {
+    assert!(level < thresholds.len(), "no such level {}", level);
+    match level {
+        0 => 0,
+        _ => thresholds[level - 1],
+    }
+}
+
+/// Evaluate the `order`-th derivative of the polynomial `f(x)` with coefficients `coefs`.
+pub fn eval_derivative<F: FiniteField>(
+    coefs: &[F::Elem],
+    order: usize,
+    x: &F::Elem,
+    p: &F,
+) -> F::Elem {
+    Polynomial::from_coefs(coefs.to_vec(), p)
+        .derivative(order, p)
+        .eval(x, p)
+}
+
+pub fn share_hierarchical<F: FiniteField>(
+    s: &F::Elem,
+    thresholds: &[usize], // Cumulative threshold of each level, strictly increasing.
+    levels: &[usize],     // Level of each member. Member ids are `1..=levels.len()`.
+    p: &F,
+) -> Vec<HierarchicalShare<F::Elem>> {
+    share_hierarchical_with_rng(s, thresholds, levels, p, &mut rand::thread_rng())
+}
+
+/// Same as `share_hierarchical`, drawing the coefficients from `rng`.
+pub fn share_hierarchical_with_rng<F: FiniteField, R: CryptoRng + RngCore>(
+    s: &F::Elem,
+    thresholds: &[usize],
+    levels: &[usize],
+    p: &F,
+    rng: &mut R,
+) -> Vec<HierarchicalShare<F::Elem>> {
+    check_thresholds(thresholds);
+    let k = *thresholds.last().unwrap();
+    let f = Polynomial::random_with_rng(s, k, p, rng);
+    levels
+        .iter()
+        .enumerate()
+        .map(|(i, level)| {
+            let id = i + 1;
+            let order = derivative_order(*level, thresholds);
+            HierarchicalShare {
+                id,
+                level: *level,
+                val: f.derivative(order, p).eval(&p.id_to_point(id), p),
+            }
+        })
+        .collect()
+}
+
+/// Whether members `(id, level)` can reconstruct the secret.
+pub fn hierarchical_can_reconstruct<F: FiniteField>(
+    members: &[(usize, usize)],
+    thresholds: &[usize],
+    p: &F,
+) -> bool {
+    check_thresholds(thresholds);
+    '_check_levels: {
+        for (i, k_i) in thresholds.iter().enumerate() {
+            let count = members.iter().filter(|(_, level)| *level <= i).count();
+            if count < *k_i {
+                return false;
+            }
+        }
+    }
+    let k = *thresholds.last().unwrap();
+    let rows: Vec<Vec<F::Elem>> = members
+        .iter()
+        .map(|(id, level)| {
+            let mut row = birkhoff_row(*id, derivative_order(*level, thresholds), k, p);
+            row.push(p.zero());
+            row
+        })
+        .collect();
+    matches!(solve_linear(rows, k, p), Some((_, rank)) if rank == k)
+}
+
+/// Evaluate the secret (i.e. `f(0)`) from the shares of an authorized set.
+/// `None` if the set cannot reconstruct, or if the shares are inconsistent.
+pub fn reconstruct_hierarchical<F: FiniteField>(
+    shares: &[&HierarchicalShare<F::Elem>],
+    thresholds: &[usize],
+    p: &F,
+) -> Option<F::Elem> {
+    '_check_uniqueness: {
+        let ids: HashSet<usize> = shares.iter().map(|s| s.id).collect();
+        assert_eq!(shares.len(), ids.len());
+    }
+    let members: Vec<(usize, usize)> = shares.iter().map(|s| (s.id, s.level)).collect();
+    if !hierarchical_can_reconstruct(&members, thresholds, p) {
+        return None;
+    }
+    let k = *thresholds.last().unwrap();
+    let rows: Vec<Vec<F::Elem>> = shares
+        .iter()
+        .map(|s| {
+            let mut row = birkhoff_row(s.id, derivative_order(s.level, thresholds), k, p);
+            row.push(s.val.clone());
+            row
+        })
+        .collect();
+    let (coefs, _rank) = solve_linear(rows, k, p)?;
+    Some(coefs[0].clone())
+}
+
+/// Row of the Birkhoff matrix for `f^{(order)}(id)`. Column `m` is the factor of the coefficient `a_m`.
+fn birkhoff_row<F: FiniteField>(id: usize, order: usize, k: usize, p: &F) -> Vec<F::Elem> {
+    let x = p.id_to_point(id);
+    let mut row = vec![p.zero(); order.min(k)];
+    let mut pow = p.one();
+    for m in order..k {
+        row.push(p.mul(&falling_factorial(m, order, p), &pow));
+        pow = p.mul(&pow, &x);
+    }
+    row
+}
+
+fn check_thresholds(thresholds: &[usize]) {
+    assert!(!thresholds.is_empty() && thresholds[0] > 0);
+    assert!(
+        thresholds.windows(2).all(|w| w[0] < w[1]),
+        "thresholds must be strictly increasing"
+    );
+}
+
+use std::collections::HashSet;
+
+use crate::{falling_factorial, linalg::solve_linear, FiniteField, Polynomial};
+use num_bigint::BigInt;
+use rand::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ScalarField;
+    use k256::Scalar;
+
+    #[test]
+    fn test_eval_derivative() {
+        let p = crate::SmallPrimeField::new(7919);
+        // f = 5 + 3x + 2x^2 + x^3, f' = 3 + 4x + 3x^2, f'' = 4 + 6x
+        let coefs = [5, 3, 2, 1];
+        assert_eq!(eval_derivative(&coefs, 0, &10, &p), 1235);
+        assert_eq!(eval_derivative(&coefs, 1, &10, &p), 343);
+        assert_eq!(eval_derivative(&coefs, 2, &10, &p), 64);
+        assert_eq!(eval_derivative(&coefs, 4, &10, &p), 0);
+    }
+
+    #[test]
+    fn test_hierarchical() {
+        // 2 of the board, and 4 members in total.
+        let thresholds = [2, 4];
+        let levels = [0, 0, 0, 1, 1, 1, 1];
+        let s = Scalar::from(1145141919810893u64);
+        let shares = share_hierarchical(&s, &thresholds, &levels, &ScalarField);
+        assert_eq!(shares[4].level, 1);
+
+        let view = [&shares[0], &shares[2], &shares[3], &shares[6]];
+        assert_eq!(
+            reconstruct_hierarchical(&view, &thresholds, &ScalarField),
+            Some(s)
+        );
+        let view = [&shares[0], &shares[1], &shares[2], &shares[5]];
+        assert_eq!(
+            reconstruct_hierarchical(&view, &thresholds, &ScalarField),
+            Some(s)
+        );
+        let view: Vec<&HierarchicalShare<Scalar>> = shares.iter().collect();
+        assert_eq!(
+            reconstruct_hierarchical(&view, &thresholds, &ScalarField),
+            Some(s)
+        );
+
+        // Only one board member.
+        let view = [&shares[0], &shares[3], &shares[4], &shares[5], &shares[6]];
+        assert!(!hierarchical_can_reconstruct(
+            &[(1, 0), (4, 1), (5, 1), (6, 1), (7, 1)],
+            &thresholds,
+            &ScalarField
+        ));
+        assert_eq!(
+            reconstruct_hierarchical(&view, &thresholds, &ScalarField),
+            None
+        );
+
+        // Too few members.
+        let view = [&shares[0], &shares[1], &shares[4]];
+        assert_eq!(
+            reconstruct_hierarchical(&view, &thresholds, &ScalarField),
+            None
+        );
+    }
+}
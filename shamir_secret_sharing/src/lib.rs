@@ -1,32 +1,64 @@
-#[derive(Debug, Clone)]
-pub struct ShamirShare {
+mod field;
+pub use field::*;
+mod gf256;
+mod linalg;
+pub use gf256::*;
+mod encoding;
+pub use encoding::*;
+mod repair;
+pub use repair::*;
+mod robust;
+pub use robust::*;
+mod packed;
+pub use packed::*;
+mod weighted;
+pub use weighted::*;
+mod hierarchical;
+pub use hierarchical::*;
+mod polynomial;
+pub use polynomial::*;
+mod conversion;
+pub use conversion::*;
+mod ntt;
+pub use ntt::*;
+mod subproduct;
+pub use subproduct::*;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ShamirShare<T = BigInt> {
     /// Unique and Non-Zero.
     /// Because id is used as argument `x` for polynomial `f(x)`, and `f(0)` is the secret.
     pub id: usize,
 
-    pub val: BigInt,
+    pub val: T,
 }
 
-pub fn share_secret(
-    s: &BigInt,
+pub fn share_secret<F: FiniteField>(
+    s: &F::Elem,
     k: usize, // (quorum) Need >=k shares to reconstruct the secret.
     n: usize, // Total number of shares.
-    p: &BigInt,
-) -> Vec<ShamirShare> {
+    p: &F,
+) -> Vec<ShamirShare<F::Elem>> {
+    share_secret_with_rng(s, k, n, p, &mut rand::thread_rng())
+}
+
+/// Same as `share_secret`, drawing the coefficients from `rng`.
+pub fn share_secret_with_rng<F: FiniteField, R: CryptoRng + RngCore>(
+    s: &F::Elem,
+    k: usize,
+    n: usize,
+    p: &F,
+    rng: &mut R,
+) -> Vec<ShamirShare<F::Elem>> {
     assert!(k <= n);
-    assert!(*p > const_0());
-    let mut coefs: Vec<BigInt> = vec![s.clone()];
-    let mut rng = rand::thread_rng();
-    for _ in 1..k {
-        let coef = rng.gen_bigint_range(&const_1(), &p);
-        coefs.push(coef);
-    }
-    let mut shares: Vec<ShamirShare> = Vec::new();
-    for id in 1..=n {
-        let x = BigInt::from(id);
+    // Panics in `elem` unless the ids `1..=n` map to distinct non-zero elements.
+    let xs: Vec<F::Elem> = (1..=n).map(|id| p.id_to_point(id)).collect();
+    let f = Polynomial::random_with_rng(s, k, p, rng);
+    let mut shares: Vec<ShamirShare<F::Elem>> = Vec::new();
+    for (id, x) in (1..=n).zip(xs.iter()) {
         let share = ShamirShare {
             id,
-            val: eval_polynomial(&coefs, &x, p),
+            val: f.eval(x, p),
         };
         shares.push(share);
     }
@@ -39,7 +71,7 @@ pub fn share_secret(
 ///     \prod_{j=0, j \neq i}^{k-1}{\frac{x_j}{x_j - x_i}}
 /// }
 /// $$
-pub fn lagrange_interpolate(shares: &[&ShamirShare], p: &BigInt) -> BigInt {
+pub fn lagrange_interpolate<F: FiniteField>(shares: &[&ShamirShare<F::Elem>], p: &F) -> F::Elem {
     '_check_uniqueness: {
         let shares_set: HashSet<usize> = shares
             .iter()
@@ -47,39 +79,63 @@ pub fn lagrange_interpolate(shares: &[&ShamirShare], p: &BigInt) -> BigInt {
             .collect();
         assert_eq!(shares.len(), shares_set.len());
     }
-    let mut sum: BigInt = const_0();
-    for (i, share) in shares.iter().enumerate() {
-        let x_i = BigInt::from(share.id);
-        let y_i = &share.val;
-        let mut λ_i: BigInt = const_1();
-        for (j, other_share) in shares.iter().enumerate() {
-            if i == j {
-                continue;
-            }
-            let x_j = BigInt::from(other_share.id);
-            let frac = moddiv(&x_j, &(&x_j - &x_i), p);
-            λ_i = (λ_i * frac).rem_euclid(p);
-        }
-        let sum_i = (y_i * λ_i).rem_euclid(p);
-        sum = (sum + sum_i).rem_euclid(p);
+    let ids: Vec<usize> = shares.iter().map(|s| s.id).collect();
+    let mut sum = p.zero();
+    for (share, λ_i) in shares.iter().zip(lagrange_coefs(&ids, p)) {
+        let sum_i = p.mul(&share.val, &λ_i);
+        sum = p.add(&sum, &sum_i);
     }
     sum
 }
 
+/// Lagrange coefficients of all `ids` for interpolating `f(0)`, in the same order.
+/// $$
+/// \lambda_i = \prod_{j \neq i}{\frac{x_j}{x_j - x_i}}
+/// $$
+///
+/// The denominators are inverted together by `FiniteField::batch_inv`, so it takes
+/// one field inversion instead of `k` (or `k^2` divisions).
+pub fn lagrange_coefs<F: FiniteField>(ids: &[usize], p: &F) -> Vec<F::Elem> {
+    '_check_uniqueness: {
+        let set: HashSet<usize> = ids.iter().copied().collect();
+        assert_eq!(set.len(), ids.len(), "duplicate ids among the members");
+    }
+    let xs: Vec<F::Elem> = ids.iter().map(|id| p.id_to_point(*id)).collect();
+    let (nums, dens): (Vec<F::Elem>, Vec<F::Elem>) = xs
+        .iter()
+        .enumerate()
+        .map(|(i, x_i)| {
+            let mut num = p.one();
+            let mut den = p.one();
+            for (j, x_j) in xs.iter().enumerate() {
+                if i != j {
+                    num = p.mul(&num, x_j);
+                    den = p.mul(&den, &p.sub(x_j, x_i));
+                }
+            }
+            (num, den)
+        })
+        .unzip();
+    p.batch_inv(&dens)
+        .iter()
+        .zip(nums.iter())
+        .map(|(den_inv, num)| p.mul(num, den_inv))
+        .collect()
+}
+
 /// Evaluate the polynomial `f(x)`, using Qin Jiushao (秦久韶) / Horner's method.
 /// Note that `coefs` is ordered by ascending power of `x`.
-pub fn eval_polynomial(coefs: &[BigInt], x: &BigInt, p: &BigInt) -> BigInt {
-    let mut y: BigInt = const_0();
+pub fn eval_polynomial<F: FiniteField>(coefs: &[F::Elem], x: &F::Elem, p: &F) -> F::Elem {
+    let mut y = p.zero();
     for coef in coefs.iter().rev() {
-        y = (y * x + coef).rem_euclid(p);
+        y = p.add(&p.mul(&y, x), coef);
     }
     y
 }
 
-use modulo_arithmetic::prelude::*;
-use modulo_arithmetic::*;
-use num_bigint::{BigInt, RandBigInt};
-use num_traits::*;
+use num_bigint::BigInt;
+use rand::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 
 #[cfg(test)]
@@ -105,5 +161,63 @@ mod tests {
         assert_eq!(es, s);
     }
 
+    #[test]
+    fn shamir_secret_sharing_generic_test() {
+        let s = Scalar::from(1145141919810893u64);
+        let shares = share_secret(&s, 3, 5, &ScalarField);
+        let view = [&shares[4], &shares[1], &shares[3]];
+        assert_eq!(lagrange_interpolate(&view, &ScalarField), s);
+
+        let p = SmallPrimeField::new(7919);
+        let shares = share_secret(&1234, 3, 5, &p);
+        let view = [&shares[0], &shares[2], &shares[4]];
+        assert_eq!(lagrange_interpolate(&view, &p), 1234);
+    }
+
+    #[test]
+    fn lagrange_coefs_test() {
+        let p = SmallPrimeField::new(7919);
+        let ids = [2, 3, 5, 7];
+        let coefs = lagrange_coefs(&ids, &p);
+        for (i, id) in ids.iter().enumerate() {
+            let mut λ_i = 1;
+            for other in ids.iter().filter(|j| *j != id) {
+                let x_j = p.id_to_point(*other);
+                λ_i = p.mul(&λ_i, &p.div(&x_j, &p.sub(&x_j, &p.id_to_point(*id))));
+            }
+            assert_eq!(coefs[i], λ_i);
+        }
+        let coefs = lagrange_coefs(&ids, &const_mersenne12());
+        let sum: BigInt = coefs.iter().sum();
+        assert_eq!(sum % const_mersenne12(), const_1());
+    }
+
+    #[test]
+    #[should_panic(expected = "duplicate ids")]
+    fn lagrange_coefs_duplicate_test() {
+        // `inv(0) == 0` by Fermat, so this would silently give all-zero coefficients.
+        lagrange_coefs(&[2, 3, 3], &SmallPrimeField::new(7919));
+    }
+
+    #[test]
+    fn shamir_secret_sharing_seeded_test() {
+        use rand::{rngs::StdRng, SeedableRng};
+        let p = SmallPrimeField::new(7919);
+        let shares1 = share_secret_with_rng(&1234, 3, 5, &p, &mut StdRng::seed_from_u64(42));
+        let shares2 = share_secret_with_rng(&1234, 3, 5, &p, &mut StdRng::seed_from_u64(42));
+        assert_eq!(shares1, shares2);
+        let shares3 = share_secret_with_rng(&1234, 3, 5, &p, &mut StdRng::seed_from_u64(43));
+        assert_ne!(shares1, shares3);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of range")]
+    fn shamir_secret_sharing_field_too_small_test() {
+        // Share 7 would be evaluated at `x == 0`, i.e. be the secret itself.
+        share_secret(&1, 2, 7, &SmallPrimeField::new(7));
+    }
+
     use super::*;
+    use k256::Scalar;
+    use modulo_arithmetic::prelude::*;
 }
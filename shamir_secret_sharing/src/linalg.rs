@@ -0,0 +1,49 @@
+//! Linear algebra over a `FiniteField`.
+
+/// Solve the augmented system `rows`, each row being `cols` coefficients followed by the constant.
+/// Free variables are set to zero. Returns a solution and the rank, or `None` if inconsistent.
+pub(crate) fn solve_linear<F: FiniteField>(
+    mut rows: Vec<Vec<F::Elem>>,
+    cols: usize,
+    p: &F,
+) -> Option<(Vec<F::Elem>, usize)> {
+    let mut pivots: Vec<usize> = Vec::new(); // pivot column of each reduced row
+    let mut r = 0;
+    for c in 0..cols {
+        let found = (r..rows.len()).find(|i| !p.is_zero(&rows[*i][c]));
+        let pivot = match found {
+            Some(i) => i,
+            None => continue,
+        };
+        rows.swap(r, pivot);
+        let inv = p.inv(&rows[r][c]);
+        for v in rows[r].iter_mut() {
+            *v = p.mul(v, &inv);
+        }
+        let pivot_row = rows[r].clone();
+        for (i, row) in rows.iter_mut().enumerate() {
+            if i == r || p.is_zero(&row[c]) {
+                continue;
+            }
+            let factor = row[c].clone();
+            for (v, pv) in row[c..].iter_mut().zip(pivot_row[c..].iter()) {
+                *v = p.sub(v, &p.mul(&factor, pv));
+            }
+        }
+        pivots.push(c);
+        r += 1;
+        if r == rows.len() {
+            break;
+        }
+    }
+    if rows[r..].iter().any(|row| !p.is_zero(&row[cols])) {
+        return None;
+    }
+    let mut sol = vec![p.zero(); cols];
+    for (i, c) in pivots.iter().enumerate() {
+        sol[*c] = rows[i][cols].clone();
+    }
+    Some((sol, pivots.len()))
+}
+
+use crate::FiniteField;
@@ -0,0 +1,148 @@
+//! Number theoretic transform, i.e. FFT over a `FiniteField` with 2-power roots of unity.
+//!
+//! Evaluating a polynomial of `len` coefficients at all `len`-th roots of unity
+//! takes `O(len log len)` field operations instead of `O(len^2)`,
+//! which makes polynomial multiplication quasi-linear.
+//!
+//! Primes `p = c * 2^e + 1` with a large `e` have roots of unity of order up to `2^e`,
+//! e.g. `SmallPrimeField::new(NTT_PRIME)`.
+
+/// `29 * 2^57 + 1`, with roots of unity of order up to `2^57`.
+pub const NTT_PRIME: u64 = 4179340454199820289;
+
+/// In-place NTT: `a[i] <- \sum_j a[j] w^{ij}`.
+/// `a.len()` must be a power of 2 and `w` a primitive `a.len()`-th root of unity.
+pub fn ntt<F: FiniteField>(a: &mut [F::Elem], w: &F::Elem, p: &F) {
+    let n = a.len();
+    assert!(n.is_power_of_two());
+    '_bit_reverse: {
+        let mut j = 0;
+        for i in 1..n {
+            let mut bit = n >> 1;
+            while j & bit != 0 {
+                j ^= bit;
+                bit >>= 1;
+            }
+            j |= bit;
+            if i < j {
+                a.swap(i, j);
+            }
+        }
+    }
+    let mut len = 2;
+    while len <= n {
+        // Primitive `len`-th root of unity.
+        let mut w_len = w.clone();
+        let mut m = n;
+        while m > len {
+            w_len = p.mul(&w_len, &w_len);
+            m >>= 1;
+        }
+        for chunk in a.chunks_mut(len) {
+            let mut w_k = p.one();
+            let (lo, hi) = chunk.split_at_mut(len / 2);
+            for (u, v) in lo.iter_mut().zip(hi.iter_mut()) {
+                let t = p.mul(v, &w_k);
+                *v = p.sub(u, &t);
+                *u = p.add(u, &t);
+                w_k = p.mul(&w_k, &w_len);
+            }
+        }
+        len <<= 1;
+    }
+}
+
+/// Inverse of `ntt` with the same `w`.
+pub fn intt<F: FiniteField>(a: &mut [F::Elem], w: &F::Elem, p: &F) {
+    ntt(a, &p.inv(w), p);
+    let n_inv = p.inv(&p.int_to_elem(a.len() as u64));
+    for v in a.iter_mut() {
+        *v = p.mul(v, &n_inv);
+    }
+}
+
+/// Product of two coefficient vectors by NTT. `None` if the field lacks a large enough root of unity.
+pub fn ntt_mul<F: FiniteField>(a: &[F::Elem], b: &[F::Elem], p: &F) -> Option<Vec<F::Elem>> {
+    if a.is_empty() || b.is_empty() {
+        return Some(vec![]);
+    }
+    let len = a.len() + b.len() - 1;
+    let n = len.next_power_of_two();
+    let w = p.root_of_unity(n)?;
+    let mut fa = a.to_vec();
+    fa.resize(n, p.zero());
+    let mut fb = b.to_vec();
+    fb.resize(n, p.zero());
+    ntt(&mut fa, &w, p);
+    ntt(&mut fb, &w, p);
+    for (x, y) in fa.iter_mut().zip(fb.iter()) {
+        *x = p.mul(x, y);
+    }
+    intt(&mut fa, &w, p);
+    fa.truncate(len);
+    Some(fa)
+}
+
+use crate::FiniteField;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{eval_polynomial, ScalarField, SmallPrimeField};
+    use k256::Scalar;
+    use modulo_arithmetic::prelude::const_mersenne12;
+    use num_bigint::BigInt;
+
+    #[test]
+    fn test_roots_of_unity() {
+        let p = SmallPrimeField::new(NTT_PRIME);
+        for log_n in [0, 1, 5, 57] {
+            let n = 1usize << log_n;
+            let w = p.root_of_unity(n).unwrap();
+            let mut pow = w;
+            for _ in 0..log_n {
+                assert_ne!(pow, 1);
+                pow = p.mul(&pow, &pow);
+            }
+            assert_eq!(pow, 1);
+        }
+        assert_eq!(p.root_of_unity(1 << 58), None);
+        assert_eq!(p.root_of_unity(3), None);
+
+        let w = ScalarField.root_of_unity(64).unwrap();
+        assert_eq!((0..6).fold(w, |acc, _| acc.square()), Scalar::ONE);
+        assert_ne!((0..5).fold(w, |acc, _| acc.square()), Scalar::ONE);
+        assert_eq!(ScalarField.root_of_unity(128), None);
+
+        let p = BigInt::from(NTT_PRIME);
+        assert!(p.root_of_unity(1 << 20).is_some());
+        assert_eq!(const_mersenne12().root_of_unity(4), None);
+    }
+
+    #[test]
+    fn test_ntt() {
+        let p = SmallPrimeField::new(NTT_PRIME);
+        let coefs: Vec<u64> = (1..=16).collect();
+        let w = p.root_of_unity(16).unwrap();
+        let mut a = coefs.clone();
+        ntt(&mut a, &w, &p);
+        let mut x = 1;
+        for v in a.iter() {
+            assert_eq!(*v, eval_polynomial(&coefs, &x, &p));
+            x = p.mul(&x, &w);
+        }
+        intt(&mut a, &w, &p);
+        assert_eq!(a, coefs);
+
+        let a: Vec<u64> = (0..100).map(|i| i * i + 7).collect();
+        let b: Vec<u64> = (0..37).map(|i| NTT_PRIME - i).collect();
+        let mut expected = vec![0; a.len() + b.len() - 1];
+        for (i, x) in a.iter().enumerate() {
+            for (j, y) in b.iter().enumerate() {
+                expected[i + j] = p.add(&expected[i + j], &p.mul(x, y));
+            }
+        }
+        assert_eq!(ntt_mul(&a, &b, &p), Some(expected));
+        assert_eq!(ntt_mul(&a, &b, &SmallPrimeField::new(7919)), None);
+    }
+}
@@ -0,0 +1,114 @@
+//! Packed (Franklin–Yung) secret sharing.
+//!
+//! `l` secrets are placed at the reserved points `x = 0, -1, ..., -(l-1)` of one polynomial `f`
+//! of degree `k-1`, whose other `k-l` degrees of freedom are random values at `x = -l, ..., -(k-1)`.
+//! Each member `id` gets the single element `f(id)`, so a share carries `l` secrets at once.
+//!
+//! Any `k` shares recover all the secrets, while any `k-l` shares reveal nothing.
+//! The reserved points must differ from the ids, e.g. `p > n + k` in a prime field.
+
+fn reserved_point<F: FiniteField>(j: usize, p: &F) -> F::Elem {
+    p.neg(&p.id_to_point(j))
+}
+
+pub fn share_packed<F: FiniteField>(
+    secrets: &[F::Elem],
+    k: usize, // (quorum) Need >=k shares to reconstruct the secrets.
+    n: usize, // Total number of shares.
+    p: &F,
+) -> Vec<ShamirShare<F::Elem>> {
+    share_packed_with_rng(secrets, k, n, p, &mut rand::thread_rng())
+}
+
+/// Same as `share_packed`, drawing the random points from `rng`.
+pub fn share_packed_with_rng<F: FiniteField, R: CryptoRng + RngCore>(
+    secrets: &[F::Elem],
+    k: usize,
+    n: usize,
+    p: &F,
+    rng: &mut R,
+) -> Vec<ShamirShare<F::Elem>> {
+    let l = secrets.len();
+    assert!(1 <= l && l <= k && k <= n);
+
+    let xs: Vec<F::Elem> = (0..k).map(|j| reserved_point(j, p)).collect();
+    let points: Vec<F::Elem> = (1..=n).map(|id| p.id_to_point(id)).collect();
+    '_check_reserved_points: {
+        for (id, x) in (1..=n).zip(points.iter()) {
+            assert!(!xs.contains(x), "id {} collides with a reserved point", id);
+        }
+    }
+    let mut ys: Vec<F::Elem> = secrets.to_vec();
+    for _ in l..k {
+        ys.push(p.random_uniform(rng));
+    }
+
+    let poly = Polynomial::interpolate(&xs, &ys, p);
+    (1..=n)
+        .zip(poly.eval_many(&points, p))
+        .map(|(id, val)| ShamirShare { id, val })
+        .collect()
+}
+
+/// Recover the `l` secrets from `>= k` shares.
+pub fn reconstruct_packed<F: FiniteField>(
+    shares: &[&ShamirShare<F::Elem>],
+    l: usize,
+    p: &F,
+) -> Vec<F::Elem> {
+    '_check_uniqueness: {
+        let ids: HashSet<usize> = shares.iter().map(|s| s.id).collect();
+        assert_eq!(shares.len(), ids.len());
+    }
+    let xs: Vec<F::Elem> = shares.iter().map(|s| p.id_to_point(s.id)).collect();
+    let ys: Vec<F::Elem> = shares.iter().map(|s| s.val.clone()).collect();
+    (0..l)
+        .map(|j| Polynomial::interpolate_at(&xs, &ys, &reserved_point(j, p), p))
+        .collect()
+}
+
+use std::collections::HashSet;
+
+use crate::{FiniteField, Polynomial, ShamirShare};
+use rand::{CryptoRng, RngCore};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ScalarField, SmallPrimeField};
+    use k256::Scalar;
+
+    #[test]
+    fn test_packed() {
+        let secrets: Vec<Scalar> = (0..10u64).map(|i| Scalar::from(1000 + i)).collect();
+        let (k, n) = (14, 20);
+        let shares = share_packed(&secrets, k, n, &ScalarField);
+        assert_eq!(shares.len(), n);
+
+        let view: Vec<&ShamirShare<Scalar>> = shares.iter().rev().take(k).collect();
+        assert_eq!(
+            reconstruct_packed(&view, secrets.len(), &ScalarField),
+            secrets
+        );
+
+        let view: Vec<&ShamirShare<Scalar>> = shares.iter().collect();
+        assert_eq!(
+            reconstruct_packed(&view, secrets.len(), &ScalarField),
+            secrets
+        );
+
+        // Fewer than k shares give garbage.
+        let view: Vec<&ShamirShare<Scalar>> = shares.iter().take(k - 1).collect();
+        assert_ne!(
+            reconstruct_packed(&view, secrets.len(), &ScalarField),
+            secrets
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "collides with a reserved point")]
+    fn test_packed_field_too_small() {
+        let p = SmallPrimeField::new(13);
+        share_packed(&[1, 2, 3], 5, 10, &p);
+    }
+}
@@ -0,0 +1,345 @@
+//! Polynomials over a `FiniteField`.
+//!
+//! The field is a runtime value (e.g. a `BigInt` modulus), so arithmetic takes it as an argument
+//! instead of going through `std::ops`.
+//!
+//! Over fields with 2-power roots of unity, large products go through the NTT,
+//! and large divisions through Newton iteration on the reversed divisor.
+
+/// Operands shorter than this use schoolbook multiplication and division.
+const FAST_THRESHOLD: usize = 64;
+
+/// Coefficients are ordered by ascending power of `x`, without trailing zeros.
+/// The zero polynomial has no coefficients.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Polynomial<T = BigInt> {
+    coefs: Vec<T>,
+}
+
+impl<T: Clone + Debug + PartialEq> Polynomial<T> {
+    pub fn zero() -> Self {
+        Self { coefs: vec![] }
+    }
+
+    pub fn from_coefs<F: FiniteField<Elem = T>>(coefs: Vec<T>, p: &F) -> Self {
+        let mut poly = Self { coefs };
+        poly.trim(p);
+        poly
+    }
+
+    /// The monic polynomial `\prod_i (x - roots_i)`.
+    pub fn from_roots<F: FiniteField<Elem = T>>(roots: &[T], p: &F) -> Self {
+        let mut coefs = vec![p.one()];
+        for r in roots.iter() {
+            // (c_0 + c_1 x + ...) * (x - r)
+            coefs.insert(0, p.zero());
+            for i in 0..coefs.len() - 1 {
+                let term = p.mul(&coefs[i + 1], r);
+                coefs[i] = p.sub(&coefs[i], &term);
+            }
+        }
+        Self { coefs }
+    }
+
+    /// Random polynomial of degree `< k` with constant term `s`, as dealt by `share_secret`.
+    pub fn random_with_rng<F: FiniteField<Elem = T>, R: CryptoRng + RngCore>(
+        s: &T,
+        k: usize,
+        p: &F,
+        rng: &mut R,
+    ) -> Self {
+        let mut coefs = vec![s.clone()];
+        for _ in 1..k {
+            coefs.push(p.random_uniform(rng));
+        }
+        Self::from_coefs(coefs, p)
+    }
+
+    pub fn coefs(&self) -> &[T] {
+        &self.coefs
+    }
+
+    /// `None` for the zero polynomial.
+    pub fn degree(&self) -> Option<usize> {
+        self.coefs.len().checked_sub(1)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.coefs.is_empty()
+    }
+
+    pub fn eval<F: FiniteField<Elem = T>>(&self, x: &T, p: &F) -> T {
+        eval_polynomial(&self.coefs, x, p)
+    }
+
+    pub fn eval_many<F: FiniteField<Elem = T>>(&self, xs: &[T], p: &F) -> Vec<T> {
+        xs.iter().map(|x| self.eval(x, p)).collect()
+    }
+
+    pub fn add<F: FiniteField<Elem = T>>(&self, other: &Self, p: &F) -> Self {
+        let len = self.coefs.len().max(other.coefs.len());
+        let zero = p.zero();
+        let coefs = (0..len)
+            .map(|i| {
+                let a = self.coefs.get(i).unwrap_or(&zero);
+                let b = other.coefs.get(i).unwrap_or(&zero);
+                p.add(a, b)
+            })
+            .collect();
+        Self::from_coefs(coefs, p)
+    }
+
+    pub fn sub<F: FiniteField<Elem = T>>(&self, other: &Self, p: &F) -> Self {
+        self.add(&other.neg(p), p)
+    }
+
+    pub fn neg<F: FiniteField<Elem = T>>(&self, p: &F) -> Self {
+        Self {
+            coefs: self.coefs.iter().map(|c| p.neg(c)).collect(),
+        }
+    }
+
+    pub fn mul<F: FiniteField<Elem = T>>(&self, other: &Self, p: &F) -> Self {
+        if self.is_zero() || other.is_zero() {
+            return Self::zero();
+        }
+        if self.coefs.len().min(other.coefs.len()) >= FAST_THRESHOLD {
+            if let Some(coefs) = ntt_mul(&self.coefs, &other.coefs, p) {
+                return Self::from_coefs(coefs, p);
+            }
+        }
+        let mut coefs = vec![p.zero(); self.coefs.len() + other.coefs.len() - 1];
+        for (i, a) in self.coefs.iter().enumerate() {
+            for (j, b) in other.coefs.iter().enumerate() {
+                coefs[i + j] = p.add(&coefs[i + j], &p.mul(a, b));
+            }
+        }
+        Self::from_coefs(coefs, p)
+    }
+
+    /// Multiply every coefficient by `c`.
+    pub fn scale<F: FiniteField<Elem = T>>(&self, c: &T, p: &F) -> Self {
+        let coefs = self.coefs.iter().map(|a| p.mul(a, c)).collect();
+        Self::from_coefs(coefs, p)
+    }
+
+    /// Division with remainder, `self == q * divisor + r` with `deg r < deg divisor`.
+    /// Panics if `divisor` is zero.
+    pub fn divrem<F: FiniteField<Elem = T>>(&self, divisor: &Self, p: &F) -> (Self, Self) {
+        let db = divisor.degree().expect("division by the zero polynomial");
+        let mut rem: Vec<T> = self.coefs.clone();
+        if rem.len() <= db {
+            return (Self::zero(), self.clone());
+        }
+        let quo_len = rem.len() - db;
+        if db.min(quo_len) >= FAST_THRESHOLD
+            && p.root_of_unity((2 * rem.len()).next_power_of_two())
+                .is_some()
+        {
+            return self.divrem_newton(divisor, p);
+        }
+        let lead_inv = p.inv(&divisor.coefs[db]);
+        let mut quo = vec![p.zero(); rem.len() - db];
+        for i in (0..quo.len()).rev() {
+            let coef = p.mul(&rem[i + db], &lead_inv);
+            for (j, b_j) in divisor.coefs.iter().enumerate() {
+                rem[i + j] = p.sub(&rem[i + j], &p.mul(&coef, b_j));
+            }
+            quo[i] = coef;
+        }
+        rem.truncate(db);
+        (Self::from_coefs(quo, p), Self::from_coefs(rem, p))
+    }
+
+    /// `rev(q) = rev(self) * rev(divisor)^{-1} mod x^{deg q + 1}`,
+    /// where `rev` reverses the coefficients.
+    fn divrem_newton<F: FiniteField<Elem = T>>(&self, divisor: &Self, p: &F) -> (Self, Self) {
+        let quo_len = self.coefs.len() - divisor.coefs.len() + 1;
+        let rev_a = Self::from_coefs(self.coefs.iter().rev().cloned().collect(), p);
+        let rev_b = Self::from_coefs(divisor.coefs.iter().rev().cloned().collect(), p);
+        let rev_q = rev_a
+            .mul(&rev_b.inv_series(quo_len, p), p)
+            .truncated(quo_len, p);
+        let mut quo: Vec<T> = rev_q.coefs;
+        quo.resize(quo_len, p.zero());
+        quo.reverse();
+        let quo = Self::from_coefs(quo, p);
+        let rem = self.sub(&quo.mul(divisor, p), p);
+        (quo, rem)
+    }
+
+    /// `g` with `self * g == 1 mod x^len`. The constant term must be non-zero.
+    fn inv_series<F: FiniteField<Elem = T>>(&self, len: usize, p: &F) -> Self {
+        let two = Self::from_coefs(vec![p.int_to_elem(2)], p);
+        let mut g = Self::from_coefs(vec![p.inv(&self.coefs[0])], p);
+        let mut l = 1;
+        while l < len {
+            l = (2 * l).min(len);
+            // g <- g (2 - self g) mod x^l
+            let fg = self.truncated(l, p).mul(&g, p).truncated(l, p);
+            g = g.mul(&two.sub(&fg, p), p).truncated(l, p);
+        }
+        g
+    }
+
+    /// `self mod x^len`
+    fn truncated<F: FiniteField<Elem = T>>(&self, len: usize, p: &F) -> Self {
+        let mut coefs = self.coefs.clone();
+        coefs.truncate(len);
+        Self::from_coefs(coefs, p)
+    }
+
+    /// The `order`-th derivative.
+    pub fn derivative<F: FiniteField<Elem = T>>(&self, order: usize, p: &F) -> Self {
+        let coefs = self
+            .coefs
+            .iter()
+            .enumerate()
+            .skip(order)
+            .map(|(m, coef)| p.mul(&falling_factorial(m, order, p), coef))
+            .collect();
+        Self::from_coefs(coefs, p)
+    }
+
+    /// The unique polynomial of degree `< xs.len()` through the points `(xs[i], ys[i])`.
+    /// $$
+    /// f(x) = \sum_i y_i \cdot \frac{M(x) / (x - x_i)}{M'(x_i)}, \quad M(x) = \prod_i (x - x_i)
+    /// $$
+    pub fn interpolate<F: FiniteField<Elem = T>>(xs: &[T], ys: &[T], p: &F) -> Self {
+        check_points(xs, ys);
+        let master = Self::from_roots(xs, p);
+        let mut res = Self::zero();
+        for (x_i, y_i) in xs.iter().zip(ys.iter()) {
+            let (basis, _) = master.divrem(&Self::from_roots(std::slice::from_ref(x_i), p), p);
+            let den = basis.eval(x_i, p);
+            res = res.add(&basis.scale(&p.div(y_i, &den), p), p);
+        }
+        res
+    }
+
+    /// `f(x)` of the polynomial through the points `(xs[i], ys[i])`, without computing its coefficients.
+    pub fn interpolate_at<F: FiniteField<Elem = T>>(xs: &[T], ys: &[T], x: &T, p: &F) -> T {
+        check_points(xs, ys);
+        let mut sum = p.zero();
+        for (i, (x_i, y_i)) in xs.iter().zip(ys.iter()).enumerate() {
+            let mut num = p.one();
+            let mut den = p.one();
+            for (j, x_j) in xs.iter().enumerate() {
+                if i == j {
+                    continue;
+                }
+                num = p.mul(&num, &p.sub(x, x_j));
+                den = p.mul(&den, &p.sub(x_i, x_j));
+            }
+            sum = p.add(&sum, &p.mul(y_i, &p.div(&num, &den)));
+        }
+        sum
+    }
+
+    /// The polynomial behind `shares`, of degree `< shares.len()`.
+    pub fn from_shares<F: FiniteField<Elem = T>>(shares: &[&ShamirShare<T>], p: &F) -> Self {
+        let xs: Vec<T> = shares.iter().map(|s| p.id_to_point(s.id)).collect();
+        let ys: Vec<T> = shares.iter().map(|s| s.val.clone()).collect();
+        Self::interpolate(&xs, &ys, p)
+    }
+
+    fn trim<F: FiniteField<Elem = T>>(&mut self, p: &F) {
+        while self.coefs.last().is_some_and(|c| p.is_zero(c)) {
+            self.coefs.pop();
+        }
+    }
+}
+
+fn check_points<T: PartialEq>(xs: &[T], ys: &[T]) {
+    assert_eq!(xs.len(), ys.len());
+    for (i, x) in xs.iter().enumerate() {
+        assert!(!xs[..i].contains(x), "duplicate interpolation points");
+    }
+}
+
+/// `m (m-1) ... (m-d+1)`, embedded with [`FiniteField::int_to_elem`] so that it is
+/// taken in the field's characteristic.
+pub fn falling_factorial<F: FiniteField>(m: usize, d: usize, p: &F) -> F::Elem {
+    ((m + 1 - d)..=m).fold(p.one(), |acc, j| p.mul(&acc, &p.int_to_elem(j as u64)))
+}
+
+use std::fmt::Debug;
+
+use crate::{eval_polynomial, ntt::ntt_mul, FiniteField, ShamirShare};
+use num_bigint::BigInt;
+use rand::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{share_secret, Gf256, ScalarField, SmallPrimeField};
+    use k256::Scalar;
+
+    #[test]
+    fn test_polynomial_arithmetic() {
+        let p = SmallPrimeField::new(7919);
+        let a = Polynomial::from_coefs(vec![1, 2, 3], &p); // 1 + 2x + 3x^2
+        let b = Polynomial::from_coefs(vec![7918, 1], &p); // x - 1
+        assert_eq!(a.degree(), Some(2));
+        assert_eq!(a.eval(&2, &p), 17);
+
+        assert_eq!(a.add(&b, &p).coefs(), &[0, 3, 3]);
+        assert_eq!(a.sub(&a, &p), Polynomial::zero());
+        assert_eq!(a.mul(&b, &p).coefs(), &[7918, 7918, 7918, 3]);
+        assert_eq!(a.scale(&2, &p).coefs(), &[2, 4, 6]);
+        assert_eq!(a.derivative(1, &p).coefs(), &[2, 6]);
+        assert_eq!(Polynomial::from_roots(&[1], &p), b);
+
+        let (q, r) = a.divrem(&b, &p);
+        assert_eq!(q.coefs(), &[5, 3]);
+        assert_eq!(r.coefs(), &[6]);
+        assert_eq!(q.mul(&b, &p).add(&r, &p), a);
+        assert_eq!(b.divrem(&a, &p), (Polynomial::zero(), b.clone()));
+
+        assert_eq!(a.eval_many(&[0, 1, 2], &p), vec![1, 6, 17]);
+    }
+
+    #[test]
+    fn test_derivative_small_characteristic() {
+        // d/dx x^2 = 2x = 0 in characteristic 2
+        let x2 = Polynomial::from_coefs(vec![0u8, 0, 1], &Gf256);
+        assert_eq!(x2.derivative(1, &Gf256), Polynomial::zero());
+        // d/dx x^3 = 3x^2 = x^2
+        let x3 = Polynomial::from_coefs(vec![0u8, 0, 0, 1], &Gf256);
+        assert_eq!(x3.derivative(1, &Gf256).coefs(), &[0, 0, 1]);
+
+        // degree >= p: d/dx x^8 = 8x^7 = x^7 and d^2/dx^2 x^8 = 56x^6 = 0 mod 7
+        let p = SmallPrimeField::new(7);
+        let mut coefs = vec![0; 9];
+        coefs[8] = 1;
+        let x8 = Polynomial::from_coefs(coefs, &p);
+        assert_eq!(x8.derivative(1, &p).coefs(), &[0, 0, 0, 0, 0, 0, 0, 1]);
+        assert_eq!(x8.derivative(2, &p), Polynomial::zero());
+        assert_eq!(x8.derivative(9, &p), Polynomial::zero());
+    }
+
+    #[test]
+    fn test_polynomial_interpolate() {
+        let s = Scalar::from(1145141919810893u64);
+        let shares = share_secret(&s, 4, 6, &ScalarField);
+        let view = [&shares[5], &shares[0], &shares[3], &shares[2]];
+        let f = Polynomial::from_shares(&view, &ScalarField);
+        assert_eq!(f.degree(), Some(3));
+        assert_eq!(f.coefs()[0], s);
+        for share in shares.iter() {
+            assert_eq!(
+                f.eval(&Scalar::from(share.id as u64), &ScalarField),
+                share.val
+            );
+        }
+
+        let xs: Vec<Scalar> = view.iter().map(|s| Scalar::from(s.id as u64)).collect();
+        let ys: Vec<Scalar> = view.iter().map(|s| s.val).collect();
+        let x = Scalar::from(5u64);
+        assert_eq!(
+            Polynomial::interpolate_at(&xs, &ys, &x, &ScalarField),
+            shares[4].val
+        );
+    }
+}
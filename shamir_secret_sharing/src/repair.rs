@@ -0,0 +1,173 @@
+//! Share repair: rebuild the share `f(j)` of a lost member `j`
+//! from `k` helpers, without revealing `f(0)` or `f(j)` to anyone but `j`.
+//!
+//! 1. Each helper `i` computes `δ_i = λ_i(j) * f(i)`, so that `f(j) = \sum_i δ_i`,
+//!    and splits `δ_i` into random additive pieces `δ_{i,l}`, one for each helper `l`.
+//! 2. Each helper `l` sums the pieces it received, `σ_l = \sum_i δ_{i,l}`,
+//!    and sends `σ_l` to `j`.
+//! 3. `j` recovers `f(j) = \sum_l σ_l`.
+//!
+//! A helper only sees uniformly masked pieces, and `j` only sees sums of them.
+
+/// Lagrange basis polynomial of `id` over `ids`, evaluated at `x`.
+/// $$
+/// \lambda_i(x) = \prod_{j \in ids, j \neq i}{\frac{x - x_j}{x_i - x_j}}
+/// $$
+pub fn lagrange_basis_at<F: FiniteField>(id: usize, ids: &[usize], x: &F::Elem, p: &F) -> F::Elem {
+    assert!(ids.contains(&id), "id {} is not among the members", id);
+    let x_i = p.id_to_point(id);
+    let mut num = p.one();
+    let mut den = p.one();
+    for j in ids.iter() {
+        if *j == id {
+            continue;
+        }
+        let x_j = p.id_to_point(*j);
+        num = p.mul(&num, &p.sub(x, &x_j));
+        den = p.mul(&den, &p.sub(&x_i, &x_j));
+    }
+    p.div(&num, &den)
+}
+
+/// Step 1, by helper `share.id`. Returns the pieces of `δ_i`, keyed by the receiving helper.
+pub fn repair_pieces<F: FiniteField>(
+    share: &ShamirShare<F::Elem>,
+    helpers: &[usize],
+    lost_id: usize,
+    p: &F,
+) -> HashMap<usize, F::Elem> {
+    repair_pieces_with_rng(share, helpers, lost_id, p, &mut rand::thread_rng())
+}
+
+/// Same as `repair_pieces`, drawing the masks from `rng`.
+pub fn repair_pieces_with_rng<F: FiniteField, R: CryptoRng + RngCore>(
+    share: &ShamirShare<F::Elem>,
+    helpers: &[usize],
+    lost_id: usize,
+    p: &F,
+    rng: &mut R,
+) -> HashMap<usize, F::Elem> {
+    '_check_helpers: {
+        let set: HashSet<usize> = helpers.iter().cloned().collect();
+        assert_eq!(set.len(), helpers.len(), "duplicate helpers");
+        assert!(!set.contains(&lost_id), "the lost member cannot help");
+        assert!(lost_id != 0, "id 0 is the secret itself");
+    }
+    let x = p.id_to_point(lost_id);
+    let delta = p.mul(&lagrange_basis_at(share.id, helpers, &x, p), &share.val);
+
+    let mut pieces: HashMap<usize, F::Elem> = HashMap::new();
+    let mut rest = delta;
+    for l in helpers[1..].iter() {
+        let piece = p.random_uniform(rng); // zero too, or pieces would leak `δ_i`
+        rest = p.sub(&rest, &piece);
+        pieces.insert(*l, piece);
+    }
+    pieces.insert(helpers[0], rest);
+    pieces
+}
+
+/// Step 2, by each helper. Sum the pieces received from all helpers (including itself).
+pub fn repair_sum<F: FiniteField>(pieces: &[F::Elem], p: &F) -> F::Elem {
+    pieces
+        .iter()
+        .fold(p.zero(), |acc, piece| p.add(&acc, piece))
+}
+
+/// Step 3, by the lost member. Sum `σ_l` from all helpers.
+pub fn repair_finish<F: FiniteField>(
+    sigmas: &[F::Elem],
+    lost_id: usize,
+    p: &F,
+) -> ShamirShare<F::Elem> {
+    ShamirShare {
+        id: lost_id,
+        val: repair_sum(sigmas, p),
+    }
+}
+
+use std::collections::{HashMap, HashSet};
+
+use crate::{FiniteField, ShamirShare};
+use rand::{CryptoRng, RngCore};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{lagrange_interpolate, share_secret, Gf256};
+    use modulo_arithmetic::prelude::const_mersenne12;
+    use num_bigint::BigInt;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    fn repair<F: FiniteField>(
+        shares: &[ShamirShare<F::Elem>],
+        helpers: &[usize],
+        lost_id: usize,
+        p: &F,
+    ) -> ShamirShare<F::Elem> {
+        // Step 1.
+        let all_pieces: Vec<HashMap<usize, F::Elem>> = helpers
+            .iter()
+            .map(|i| repair_pieces(&shares[i - 1], helpers, lost_id, p))
+            .collect();
+
+        // Step 2.
+        let sigmas: Vec<F::Elem> = helpers
+            .iter()
+            .map(|l| {
+                let received: Vec<F::Elem> = all_pieces.iter().map(|m| m[l].clone()).collect();
+                repair_sum(&received, p)
+            })
+            .collect();
+
+        // Step 3.
+        repair_finish(&sigmas, lost_id, p)
+    }
+
+    #[test]
+    fn test_repair() {
+        let p = const_mersenne12();
+        let s = BigInt::from(1145141919810893i64);
+        let shares = share_secret(&s, 3, 6, &p);
+
+        let lost_id = 4;
+        let repaired = repair(&shares, &[1, 3, 6], lost_id, &p);
+        assert_eq!(repaired, shares[lost_id - 1]);
+
+        // The repaired share works like the original.
+        let view = [&repaired, &shares[1], &shares[4]];
+        assert_eq!(lagrange_interpolate(&view, &p), s);
+    }
+
+    #[test]
+    fn test_repair_seeded() {
+        let p = const_mersenne12();
+        let shares = share_secret(&BigInt::from(1145141919810893i64), 3, 6, &p);
+        let pieces = |seed: u64| {
+            let mut rng = StdRng::seed_from_u64(seed);
+            repair_pieces_with_rng(&shares[0], &[1, 3, 6], 4, &p, &mut rng)
+        };
+        assert_eq!(pieces(42), pieces(42));
+        assert_ne!(pieces(42), pieces(43));
+    }
+
+    #[test]
+    fn test_repair_gf256() {
+        let shares = share_secret(&0x42, 2, 5, &Gf256);
+        let repaired = repair(&shares, &[2, 5], 3, &Gf256);
+        assert_eq!(repaired, shares[2]);
+
+        // Masks are uniform over the whole field, so in a small field
+        // a piece is sometimes zero, and `helpers[0]` sometimes gets `δ_i` itself.
+        let mut rng = StdRng::seed_from_u64(42);
+        let x = Gf256.id_to_point(3);
+        let delta = Gf256.mul(&lagrange_basis_at(2, &[2, 5], &x, &Gf256), &shares[1].val);
+        let (mut zero_mask, mut unmasked) = (false, false);
+        for _ in 0..4096 {
+            let pieces = repair_pieces_with_rng(&shares[1], &[2, 5], 3, &Gf256, &mut rng);
+            zero_mask |= pieces[&5] == 0;
+            unmasked |= pieces[&2] == delta;
+        }
+        assert!(zero_mask && unmasked);
+    }
+}
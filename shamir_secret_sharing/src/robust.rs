@@ -0,0 +1,164 @@
+//! Robust reconstruction by Reed–Solomon decoding (Berlekamp–Welch).
+//!
+//! Shares of a degree `k-1` polynomial `f` form a Reed–Solomon codeword.
+//! With `n` shares, up to `e = (n-k)/2` corrupted ones can be corrected:
+//! find a monic error locator `E` of degree `e` and `Q` of degree `< k+e` such that
+//! $$
+//! Q(x_i) = y_i \cdot E(x_i), \quad i = 1..n
+//! $$
+//! then `f = Q / E`, and the bad shares are those with `f(x_i) != y_i`.
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RobustReconstruction<T> {
+    /// `f(0)`
+    pub secret: T,
+    /// Ids of the shares that disagree with the decoded polynomial, ascending.
+    pub bad_ids: Vec<usize>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DecodeError {
+    NotEnoughShares {
+        need: usize,
+        got: usize,
+    },
+    /// More than `(n-k)/2` shares are corrupted.
+    TooManyErrors,
+}
+
+pub fn robust_interpolate<F: FiniteField>(
+    shares: &[&ShamirShare<F::Elem>],
+    k: usize, // (quorum) degree of the polynomial is k-1.
+    p: &F,
+) -> Result<RobustReconstruction<F::Elem>, DecodeError> {
+    '_check_uniqueness: {
+        let ids: HashSet<usize> = shares.iter().map(|s| s.id).collect();
+        assert_eq!(shares.len(), ids.len());
+    }
+    let n = shares.len();
+    if k == 0 || n < k {
+        return Err(DecodeError::NotEnoughShares {
+            need: k.max(1),
+            got: n,
+        });
+    }
+    let e = (n - k) / 2;
+
+    // Unknowns: q_0..q_{k+e-1}, then e_0..e_{e-1}.
+    // Row i: \sum_m q_m x_i^m - y_i \sum_{j<e} e_j x_i^j = y_i x_i^e
+    let xs: Vec<F::Elem> = shares.iter().map(|s| p.id_to_point(s.id)).collect();
+    let mut rows: Vec<Vec<F::Elem>> = Vec::with_capacity(n);
+    for (x, share) in xs.iter().zip(shares.iter()) {
+        let y = &share.val;
+        let powers = powers_of(x, k + e, p);
+        let mut row: Vec<F::Elem> = powers[..k + e].to_vec();
+        for pow in powers[..e].iter() {
+            row.push(p.neg(&p.mul(y, pow)));
+        }
+        row.push(p.mul(y, &powers[e]));
+        rows.push(row);
+    }
+    let (sol, _rank) = solve_linear(rows, k + 2 * e, p).ok_or(DecodeError::TooManyErrors)?;
+
+    let q_poly = Polynomial::from_coefs(sol[..k + e].to_vec(), p);
+    let mut e_coefs: Vec<F::Elem> = sol[k + e..].to_vec();
+    e_coefs.push(p.one());
+    let e_poly = Polynomial::from_coefs(e_coefs, p);
+    let (f_poly, rem) = q_poly.divrem(&e_poly, p);
+    if !rem.is_zero() || f_poly.coefs().len() > k {
+        return Err(DecodeError::TooManyErrors);
+    }
+
+    let mut bad_ids: Vec<usize> = xs
+        .iter()
+        .zip(shares.iter())
+        .filter(|(x, share)| f_poly.eval(x, p) != share.val)
+        .map(|(_, share)| share.id)
+        .collect();
+    if bad_ids.len() > e {
+        return Err(DecodeError::TooManyErrors);
+    }
+    bad_ids.sort();
+    let secret = f_poly.coefs().first().cloned().unwrap_or_else(|| p.zero());
+    Ok(RobustReconstruction { secret, bad_ids })
+}
+
+/// `[1, x, x^2, ..., x^deg]`
+fn powers_of<F: FiniteField>(x: &F::Elem, deg: usize, p: &F) -> Vec<F::Elem> {
+    let mut res = vec![p.one()];
+    for i in 0..deg {
+        res.push(p.mul(&res[i], x));
+    }
+    res
+}
+
+impl std::fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DecodeError::NotEnoughShares { need, got } => {
+                write!(f, "need at least {} shares, got {}", need, got)
+            }
+            DecodeError::TooManyErrors => write!(f, "too many corrupted shares to decode"),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+use std::collections::HashSet;
+
+use crate::{linalg::solve_linear, FiniteField, Polynomial, ShamirShare};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{share_secret, SmallPrimeField};
+    use modulo_arithmetic::prelude::const_mersenne12;
+    use num_bigint::BigInt;
+
+    #[test]
+    fn test_robust_interpolate() {
+        let p = const_mersenne12();
+        let s = BigInt::from(1145141919810893i64);
+        let (k, n) = (3, 9); // corrects up to 3 errors
+        let mut shares = share_secret(&s, k, n, &p);
+
+        let view: Vec<&ShamirShare> = shares.iter().collect();
+        let res = robust_interpolate(&view, k, &p).unwrap();
+        assert_eq!(res.secret, s);
+        assert!(res.bad_ids.is_empty());
+
+        shares[1].val += 1;
+        shares[4].val = BigInt::from(42);
+        shares[8].val = BigInt::from(0);
+        let view: Vec<&ShamirShare> = shares.iter().collect();
+        let res = robust_interpolate(&view, k, &p).unwrap();
+        assert_eq!(res.secret, s);
+        assert_eq!(res.bad_ids, vec![2, 5, 9]);
+
+        shares[0].val += 1;
+        let view: Vec<&ShamirShare> = shares.iter().collect();
+        assert_ne!(
+            robust_interpolate(&view, k, &p).map(|res| res.secret),
+            Ok(s)
+        );
+    }
+
+    #[test]
+    fn test_robust_interpolate_small_field() {
+        let p = SmallPrimeField::new(7919);
+        let (k, n) = (4, 7); // corrects 1 error
+        let mut shares = share_secret(&1234, k, n, &p);
+        shares[3].val = (shares[3].val + 1) % 7919;
+        let view: Vec<&ShamirShare<u64>> = shares.iter().collect();
+        let res = robust_interpolate(&view, k, &p).unwrap();
+        assert_eq!(res.secret, 1234);
+        assert_eq!(res.bad_ids, vec![4]);
+
+        let view: Vec<&ShamirShare<u64>> = shares[..3].iter().collect();
+        assert_eq!(
+            robust_interpolate(&view, k, &p),
+            Err(DecodeError::NotEnoughShares { need: 4, got: 3 })
+        );
+    }
+}
@@ -0,0 +1,207 @@
+//! Subproduct tree: multipoint evaluation and interpolation for any field.
+//!
+//! The leaves are `x - x_i`, and each node is the product of its children,
+//! so the root is `M(x) = \prod_i (x - x_i)`.
+//! * Evaluation reduces `f` modulo the nodes from the root down; the leaves hold `f(x_i)`.
+//! * Interpolation combines `y_i / M'(x_i)` from the leaves up.
+//!
+//! With the NTT products and Newton divisions of `Polynomial`,
+//! both take `O(n log^2 n)` field operations instead of `O(n^2)`.
+//! Without roots of unity they fall back to schoolbook arithmetic, still correct.
+
+#[derive(Clone, Debug)]
+pub struct SubproductTree<T> {
+    xs: Vec<T>,
+    /// `levels[0]` are the leaves, and the last level is the root alone.
+    levels: Vec<Vec<Polynomial<T>>>,
+}
+
+impl<T: Clone + Debug + PartialEq> SubproductTree<T> {
+    pub fn new<F: FiniteField<Elem = T>>(xs: &[T], p: &F) -> Self {
+        assert!(!xs.is_empty());
+        let leaves: Vec<Polynomial<T>> = xs
+            .iter()
+            .map(|x| Polynomial::from_roots(std::slice::from_ref(x), p))
+            .collect();
+        let mut levels = vec![leaves];
+        while levels.last().unwrap().len() > 1 {
+            let next: Vec<Polynomial<T>> = levels
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|pair| match pair {
+                    [a, b] => a.mul(b, p),
+                    [a] => a.clone(),
+                    _ => unreachable!(),
+                })
+                .collect();
+            levels.push(next);
+        }
+        Self {
+            xs: xs.to_vec(),
+            levels,
+        }
+    }
+
+    /// `M(x) = \prod_i (x - x_i)`
+    pub fn root(&self) -> &Polynomial<T> {
+        &self.levels.last().unwrap()[0]
+    }
+
+    /// `[f(x_0), f(x_1), ...]`
+    pub fn eval<F: FiniteField<Elem = T>>(&self, f: &Polynomial<T>, p: &F) -> Vec<T> {
+        let mut rems: Vec<Polynomial<T>> = vec![f.divrem(self.root(), p).1];
+        for level in self.levels.iter().rev().skip(1) {
+            rems = level
+                .iter()
+                .enumerate()
+                .map(|(i, node)| rems[i / 2].divrem(node, p).1)
+                .collect();
+        }
+        rems.iter()
+            .map(|r| r.coefs().first().cloned().unwrap_or_else(|| p.zero()))
+            .collect()
+    }
+
+    /// The unique polynomial of degree `< xs.len()` with `f(x_i) == ys[i]`.
+    pub fn interpolate<F: FiniteField<Elem = T>>(&self, ys: &[T], p: &F) -> Polynomial<T> {
+        assert_eq!(ys.len(), self.xs.len());
+        let weights = self.eval(&self.root().derivative(1, p), p);
+        let mut vals: Vec<Polynomial<T>> = ys
+            .iter()
+            .zip(weights.iter())
+            .map(|(y, w)| Polynomial::from_coefs(vec![p.div(y, w)], p))
+            .collect();
+        for level in self.levels.iter().take(self.levels.len() - 1) {
+            vals = vals
+                .chunks(2)
+                .zip(level.chunks(2))
+                .map(|(val, node)| match (val, node) {
+                    ([v0, v1], [m0, m1]) => v0.mul(m1, p).add(&v1.mul(m0, p), p),
+                    ([v0], [_]) => v0.clone(),
+                    _ => unreachable!(),
+                })
+                .collect();
+        }
+        vals.pop().unwrap()
+    }
+}
+
+/// Same as `share_secret`, evaluating at all ids with a subproduct tree.
+pub fn share_secret_fast<F: FiniteField>(
+    s: &F::Elem,
+    k: usize,
+    n: usize,
+    p: &F,
+) -> Vec<ShamirShare<F::Elem>> {
+    share_secret_fast_with_rng(s, k, n, p, &mut rand::thread_rng())
+}
+
+/// Same as `share_secret_fast`, drawing the coefficients from `rng`.
+pub fn share_secret_fast_with_rng<F: FiniteField, R: CryptoRng + RngCore>(
+    s: &F::Elem,
+    k: usize,
+    n: usize,
+    p: &F,
+    rng: &mut R,
+) -> Vec<ShamirShare<F::Elem>> {
+    assert!(1 <= k && k <= n);
+    let f = Polynomial::random_with_rng(s, k, p, rng);
+    let xs: Vec<F::Elem> = (1..=n).map(|id| p.id_to_point(id)).collect();
+    let tree = SubproductTree::new(&xs, p);
+    tree.eval(&f, p)
+        .into_iter()
+        .enumerate()
+        .map(|(i, val)| ShamirShare { id: i + 1, val })
+        .collect()
+}
+
+/// Same as `lagrange_interpolate`, computing all the Lagrange coefficients with a subproduct tree:
+/// $$
+/// \lambda_i = \frac{M(0)}{(0 - x_i) M'(x_i)}
+/// $$
+pub fn lagrange_interpolate_fast<F: FiniteField>(
+    shares: &[&ShamirShare<F::Elem>],
+    p: &F,
+) -> F::Elem {
+    '_check_uniqueness: {
+        let ids: HashSet<usize> = shares.iter().map(|s| s.id).collect();
+        assert_eq!(shares.len(), ids.len());
+    }
+    let xs: Vec<F::Elem> = shares.iter().map(|s| p.id_to_point(s.id)).collect();
+    let tree = SubproductTree::new(&xs, p);
+    let m_0 = tree.root().eval(&p.zero(), p);
+    let derivs = tree.eval(&tree.root().derivative(1, p), p);
+    let dens: Vec<F::Elem> = xs
+        .iter()
+        .zip(derivs.iter())
+        .map(|(x_i, d_i)| p.mul(&p.neg(x_i), d_i))
+        .collect();
+    let mut sum = p.zero();
+    for (share, den_inv) in shares.iter().zip(p.batch_inv(&dens)) {
+        let λ_i = p.mul(&m_0, &den_inv);
+        sum = p.add(&sum, &p.mul(&share.val, &λ_i));
+    }
+    sum
+}
+
+use std::{collections::HashSet, fmt::Debug};
+
+use crate::{FiniteField, Polynomial, ShamirShare};
+use rand::{CryptoRng, RngCore};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        lagrange_interpolate, share_secret, Gf256, ScalarField, SmallPrimeField, NTT_PRIME,
+    };
+    use k256::Scalar;
+
+    fn check_tree<F: FiniteField>(n: usize, p: &F) {
+        let xs: Vec<F::Elem> = (0..n).map(|i| p.id_to_point(3 * i + 5)).collect();
+        let coefs: Vec<F::Elem> = (0..n as u64).map(|i| p.int_to_elem(i * i + 1)).collect();
+        let f = Polynomial::from_coefs(coefs, p);
+        let tree = SubproductTree::new(&xs, p);
+        let ys = tree.eval(&f, p);
+        assert_eq!(ys, f.eval_many(&xs, p));
+        assert_eq!(tree.interpolate(&ys, p), f);
+    }
+
+    #[test]
+    fn test_subproduct_tree() {
+        check_tree(1, &ScalarField);
+        check_tree(7, &ScalarField);
+        check_tree(50, &SmallPrimeField::new(7919));
+        check_tree(300, &SmallPrimeField::new(NTT_PRIME)); // NTT and Newton paths
+        check_tree(40, &Gf256);
+    }
+
+    #[test]
+    fn test_fast_sharing() {
+        let p = SmallPrimeField::new(NTT_PRIME);
+        let (k, n) = (200, 500);
+        let shares = share_secret_fast(&1145141919810893, k, n, &p);
+        let view: Vec<&ShamirShare<u64>> = shares.iter().rev().take(k).collect();
+        assert_eq!(lagrange_interpolate_fast(&view, &p), 1145141919810893);
+        assert_eq!(lagrange_interpolate(&view, &p), 1145141919810893);
+
+        let s = Scalar::from(1145141919810893u64);
+        let shares = share_secret_fast(&s, 3, 5, &ScalarField);
+        let view = [&shares[4], &shares[1], &shares[3]];
+        assert_eq!(lagrange_interpolate_fast(&view, &ScalarField), s);
+    }
+
+    #[test]
+    fn test_fast_interpolate_gf256() {
+        let shares = share_secret(&0x42, 5, 9, &Gf256);
+        let view: Vec<&ShamirShare<u8>> = shares.iter().rev().take(5).collect();
+        assert_eq!(lagrange_interpolate(&view, &Gf256), 0x42);
+        assert_eq!(lagrange_interpolate_fast(&view, &Gf256), 0x42);
+
+        let shares = share_secret_fast(&0x42, 100, 255, &Gf256);
+        let view: Vec<&ShamirShare<u8>> = shares.iter().step_by(2).take(100).collect();
+        assert_eq!(lagrange_interpolate_fast(&view, &Gf256), 0x42);
+        assert_eq!(lagrange_interpolate(&view, &Gf256), 0x42);
+    }
+}
@@ -0,0 +1,122 @@
+//! Weighted threshold sharing.
+//!
+//! Party `i` with weight `w_i` holds `w_i` ordinary shares of one polynomial of degree `k-1`,
+//! so a set of parties can reconstruct iff their total weight is at least `k`.
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WeightedShare<T = BigInt> {
+    /// 1-based index of the party in `weights`.
+    pub party: usize,
+    /// `weights[party - 1]` shares with consecutive ids.
+    pub shares: Vec<ShamirShare<T>>,
+}
+
+/// Share ids held by each party: party 1 gets `1..=w_1`, party 2 the next `w_2` ids, and so on.
+pub fn weighted_ids(weights: &[usize]) -> Vec<Vec<usize>> {
+    let mut next = 1;
+    weights
+        .iter()
+        .map(|w| {
+            let ids: Vec<usize> = (next..next + w).collect();
+            next += w;
+            ids
+        })
+        .collect()
+}
+
+pub fn share_weighted<F: FiniteField>(
+    s: &F::Elem,
+    k: usize,          // (quorum) Need total weight >=k to reconstruct the secret.
+    weights: &[usize], // Weight of each party, i.e. the number of shares it holds.
+    p: &F,
+) -> Vec<WeightedShare<F::Elem>> {
+    share_weighted_with_rng(s, k, weights, p, &mut rand::thread_rng())
+}
+
+/// Same as `share_weighted`, drawing the coefficients from `rng`.
+pub fn share_weighted_with_rng<F: FiniteField, R: CryptoRng + RngCore>(
+    s: &F::Elem,
+    k: usize,
+    weights: &[usize],
+    p: &F,
+    rng: &mut R,
+) -> Vec<WeightedShare<F::Elem>> {
+    assert!(
+        weights.iter().all(|w| *w > 0),
+        "every party needs weight >= 1"
+    );
+    let n: usize = weights.iter().sum();
+    let mut shares = share_secret_with_rng(s, k, n, p, rng).into_iter();
+    weighted_ids(weights)
+        .iter()
+        .enumerate()
+        .map(|(i, ids)| WeightedShare {
+            party: i + 1,
+            shares: shares.by_ref().take(ids.len()).collect(),
+        })
+        .collect()
+}
+
+/// Whether the given parties together hold at least `k` distinct shares.
+pub fn weighted_can_reconstruct<T>(shares: &[&WeightedShare<T>], k: usize) -> bool {
+    let ids: HashSet<usize> = shares
+        .iter()
+        .flat_map(|ws| ws.shares.iter().map(|s| s.id))
+        .collect();
+    ids.len() >= k
+}
+
+/// Evaluate the secret from the shares of parties with total weight `>= k`.
+pub fn reconstruct_weighted<F: FiniteField>(shares: &[&WeightedShare<F::Elem>], p: &F) -> F::Elem {
+    '_check_uniqueness: {
+        let parties: HashSet<usize> = shares.iter().map(|ws| ws.party).collect();
+        assert_eq!(shares.len(), parties.len());
+    }
+    let view: Vec<&ShamirShare<F::Elem>> = shares.iter().flat_map(|ws| ws.shares.iter()).collect();
+    lagrange_interpolate(&view, p)
+}
+
+use std::collections::HashSet;
+
+use crate::{lagrange_interpolate, share_secret_with_rng, FiniteField, ShamirShare};
+use num_bigint::BigInt;
+use rand::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ScalarField;
+    use k256::Scalar;
+
+    #[test]
+    fn test_weighted() {
+        assert_eq!(
+            weighted_ids(&[2, 1, 3]),
+            vec![vec![1, 2], vec![3], vec![4, 5, 6]]
+        );
+
+        // Two board members of weight 3 and four ordinary members of weight 1.
+        let s = Scalar::from(1145141919810893u64);
+        let weights = [3, 3, 1, 1, 1, 1];
+        let k = 5;
+        let shares = share_weighted(&s, k, &weights, &ScalarField);
+        assert_eq!(shares[1].shares.len(), 3);
+        assert_eq!(shares[2].shares[0].id, 7);
+
+        // Both board members.
+        let view = [&shares[0], &shares[1]];
+        assert!(weighted_can_reconstruct(&view, k));
+        assert_eq!(reconstruct_weighted(&view, &ScalarField), s);
+
+        // One board member and two ordinary members.
+        let view = [&shares[4], &shares[1], &shares[2]];
+        assert!(weighted_can_reconstruct(&view, k));
+        assert_eq!(reconstruct_weighted(&view, &ScalarField), s);
+
+        // All ordinary members are not enough.
+        let view = [&shares[2], &shares[3], &shares[4], &shares[5]];
+        assert!(!weighted_can_reconstruct(&view, k));
+        assert_ne!(reconstruct_weighted(&view, &ScalarField), s);
+    }
+}